@@ -1,50 +1,315 @@
+use serde::Deserialize;
 use std::env;
+use std::fmt::Write as _;
+#[cfg(feature = "openmodelica")]
+use std::path::Path;
 use std::path::PathBuf;
+#[cfg(feature = "openmodelica")]
+use std::process::Command;
 
 fn main() {
-    // Detect OS and set paths
-    let omc_lib_search = if cfg!(target_os = "linux") {
-        // Check multiple possible locations in Linux
-        let arch = if cfg!(target_arch = "aarch64") {
-            "aarch64"
-        } else {
-            "x86_64"
-        };
+    // Typed wrappers are generated in every build
+    generate_typed_models();
+    
+    #[cfg(feature = "c-header")]
+    generate_c_header();
+    
+    // Without the `openmodelica` feature the crate is pure Rust: nothing to
+    // compile or link, and no OpenModelica installation is needed
+    #[cfg(feature = "openmodelica")]
+    build_openmodelica();
+}
+
+/// Writes the C API header to `$OUT_DIR/modelica_rust_ffi.h`
+///
+/// Never into the source tree, which may be read-only (and `cargo package`
+/// rejects build scripts that modify it). `tests/header_test.rs` checks the
+/// committed `include/modelica_rust_ffi.h` against it.
+#[cfg(feature = "c-header")]
+fn generate_c_header() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_file(PathBuf::from(&crate_dir).join("cbindgen.toml"))
+        .expect("Invalid cbindgen.toml");
+    
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("Unable to generate C header")
+        .write_to_file(PathBuf::from(env::var("OUT_DIR").unwrap()).join("modelica_rust_ffi.h"));
+}
+
+/// A model description from `models/<Name>.toml`
+#[derive(Deserialize)]
+struct ModelDescription {
+    name: String,
+    category: String,
+    variables: Vec<VariableDescription>,
+}
+
+#[derive(Deserialize)]
+struct VariableDescription {
+    name: String,
+    #[serde(rename = "type")]
+    io_type: VariableType,
+    causality: VariableCausality,
+    // Only checked here; the runtime reads the rest of the attributes
+    #[allow(dead_code)]
+    variability: VariableVariability,
+    unit: Option<String>,
+    description: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+enum VariableType {
+    Real,
+    Boolean,
+    Integer,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum VariableCausality {
+    Parameter,
+    Input,
+    Output,
+    Local,
+    Independent,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum VariableVariability {
+    Constant,
+    Fixed,
+    Tunable,
+    Discrete,
+    Continuous,
+}
+
+/// Writes `typed_models.rs`: for every `models/*.toml`, a wrapper struct
+/// with a `VariableHandle` constant and typed accessors per variable, so a
+/// misspelt variable name is a compile error instead of `VariableNotFound`
+fn generate_typed_models() {
+    let models_dir = PathBuf::from("models");
+    println!("cargo:rerun-if-changed={}", models_dir.display());
+    
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&models_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    
+    let mut out = String::from("// Generated by build.rs from models/*.toml, do not edit\n");
+    for file in &files {
+        println!("cargo:rerun-if-changed={}", file.display());
+        let text = std::fs::read_to_string(file)
+            .unwrap_or_else(|e| panic!("Failed to read {}: {}", file.display(), e));
+        let model: ModelDescription = toml::from_str(&text)
+            .unwrap_or_else(|e| panic!("Invalid model description {}: {}", file.display(), e));
         
-        vec![
-            "/usr/lib/omc".to_string(),
-            format!("/usr/lib/{}-linux-gnu/omc", arch),
-            "/usr/lib".to_string(),
-            format!("/usr/lib/{}-linux-gnu", arch),
-        ]
-    } else if cfg!(target_os = "macos") {
-        let omc_base = "/Applications/OpenModelica/build_cmake/install_cmake";
-        vec![format!("{}/lib/omc", omc_base), format!("{}/lib", omc_base)]
-    } else {
-        panic!("Unsupported OS");
-    };
+        let mut names = std::collections::HashSet::new();
+        for variable in &model.variables {
+            if !names.insert(variable.name.as_str()) {
+                panic!("{}: variable '{}' declared twice", file.display(), variable.name);
+            }
+        }
+        
+        write_typed_model(&mut out, &model, file);
+    }
     
-    let (omc_include, omc_gc_include) = if cfg!(target_os = "linux") {
-        ("/usr/include/omc/c".to_string(), "/usr/include/omc/gc".to_string())
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    std::fs::write(out_path.join("typed_models.rs"), out).expect("Couldn't write typed models!");
+}
+
+/// Methods a variable accessor must not shadow
+const RESERVED_METHODS: &[&str] = &[
+    "new", "with_parameters", "component", "component_mut", "into_component", "read",
+    "component_type", "initialize", "set_input", "set_bool_input", "get_output",
+    "step", "reset", "set_parameter", "get_all_outputs", "metadata", "time",
+];
+
+const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while", "async", "await", "dyn", "abstract", "become", "box", "do",
+    "final", "macro", "override", "priv", "typeof", "unsized", "virtual", "yield", "try",
+];
+
+/// Converts a Modelica name (`heaterOn`, `room.T`) to snake case (`heater_on`, `room_t`)
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev = if i > 0 { chars[i - 1] } else { '_' };
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if i > 0 && !out.ends_with('_')
+                && (prev.is_ascii_lowercase() || prev.is_ascii_digit() || (prev.is_ascii_uppercase() && next_lower)) {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    let out = out.trim_matches('_').to_string();
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("v_{}", out)
     } else {
-        let omc_base = "/Applications/OpenModelica/build_cmake/install_cmake";
-        (format!("{}/include/omc/c", omc_base), format!("{}/include/omc/gc", omc_base))
-    };
+        out
+    }
+}
+
+fn write_typed_model(out: &mut String, model: &ModelDescription, file: &std::path::Path) {
+    let name = &model.name;
     
-    // Add all library search paths
-    for path in &omc_lib_search {
-        if PathBuf::from(path).exists() {
-            println!("cargo:rustc-link-search=native={}", path);
-            println!("cargo:warning=Added library search path: {}", path);
+    writeln!(out).unwrap();
+    writeln!(out, "/// Typed wrapper for the `{}` model ({})", name, model.category).unwrap();
+    writeln!(out, "///").unwrap();
+    writeln!(out, "/// Generated from `{}`.", file.display().to_string().replace('\\', "/")).unwrap();
+    writeln!(out, "#[derive(Debug)]").unwrap();
+    writeln!(out, "pub struct {} {{\n    component: ModelicaComponent,\n}}", name).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "impl {} {{", name).unwrap();
+    writeln!(out, "    pub const MODEL_NAME: &'static str = {:?};", name).unwrap();
+    
+    // Handles
+    for variable in &model.variables {
+        writeln!(out).unwrap();
+        if let Some(description) = &variable.description {
+            writeln!(out, "    /// {}", description).unwrap();
+        }
+        writeln!(
+            out,
+            "    pub const {}: VariableHandle = VariableHandle {{ name: {:?}, unit: {:?}, causality: Causality::{:?} }};",
+            snake_case(&variable.name).to_uppercase(),
+            variable.name,
+            variable.unit,
+            variable.causality,
+        ).unwrap();
+    }
+    
+    // Construction
+    writeln!(out, "
+    pub fn new() -> ComponentResult<Self> {{
+        Ok(Self {{ component: ModelicaComponent::new(Self::MODEL_NAME)? }})
+    }}
+
+    /// Create the model with parameter overrides
+    pub fn with_parameters(parameters: &HashMap<String, f64>) -> ComponentResult<Self> {{
+        Ok(Self {{ component: ModelicaComponent::with_parameters(Self::MODEL_NAME, parameters)? }})
+    }}
+
+    pub fn component(&self) -> &ModelicaComponent {{
+        &self.component
+    }}
+
+    pub fn component_mut(&mut self) -> &mut ModelicaComponent {{
+        &mut self.component
+    }}
+
+    pub fn into_component(self) -> ModelicaComponent {{
+        self.component
+    }}
+
+    /// Simulation time in seconds
+    pub fn time(&self) -> f64 {{
+        self.component.runtime().time()
+    }}
+
+    fn read(&self, variable: VariableHandle) -> f64 {{
+        // The handle comes from the same description as the runtime's variable table
+        self.component.get_output(variable.name)
+            .expect(\"variable is declared in the model description\")
+    }}").unwrap();
+    
+    // Accessors
+    for variable in model.variables.iter().filter(|v| v.causality != VariableCausality::Independent) {
+        let mut method = snake_case(&variable.name);
+        if RESERVED_METHODS.contains(&method.as_str()) || KEYWORDS.contains(&method.as_str()) {
+            method.push_str("_value");
+        }
+        let handle = snake_case(&variable.name).to_uppercase();
+        let doc = match (&variable.description, &variable.unit) {
+            (Some(d), Some(u)) => format!("    /// {} [{}]\n", d, u),
+            (Some(d), None) => format!("    /// {}\n", d),
+            (None, Some(u)) => format!("    /// [{}]\n", u),
+            (None, None) => String::new(),
+        };
+        
+        let (rust_type, from_f64, to_f64) = match variable.io_type {
+            VariableType::Real => ("f64", "", ""),
+            VariableType::Integer => ("i64", " as i64", " as f64"),
+            VariableType::Boolean => ("bool", " != 0.0", ""),
+        };
+        
+        writeln!(out).unwrap();
+        write!(out, "{}", doc).unwrap();
+        writeln!(out, "    pub fn {}(&self) -> {} {{\n        self.read(Self::{}){}\n    }}", method, rust_type, handle, from_f64).unwrap();
+        
+        let setter = match (variable.causality, variable.io_type) {
+            (VariableCausality::Output, _) => None,
+            (VariableCausality::Parameter, VariableType::Boolean) => None,
+            (VariableCausality::Parameter, _) => Some(format!("self.component.set_parameter(Self::{}.name, value{})", handle, to_f64)),
+            (_, VariableType::Boolean) => Some(format!("self.component.set_bool_input(Self::{}.name, value)", handle)),
+            (_, _) => Some(format!("self.component.set_input(Self::{}.name, value{})", handle, to_f64)),
+        };
+        if let Some(body) = setter {
+            writeln!(out).unwrap();
+            write!(out, "{}", doc).unwrap();
+            writeln!(out, "    pub fn set_{}(&mut self, value: {}) -> ComponentResult<()> {{\n        {}\n    }}", method, rust_type, body).unwrap();
         }
     }
+    writeln!(out, "}}").unwrap();
+    
+    // Usable anywhere a component is expected
+    writeln!(out, "
+impl SimulationComponent for {name} {{
+    fn component_type(&self) -> &str {{ self.component.component_type() }}
+    fn initialize(&mut self) -> ComponentResult<()> {{ self.component.initialize() }}
+    fn set_input(&mut self, name: &str, value: f64) -> ComponentResult<()> {{ self.component.set_input(name, value) }}
+    fn set_bool_input(&mut self, name: &str, value: bool) -> ComponentResult<()> {{ self.component.set_bool_input(name, value) }}
+    fn get_output(&self, name: &str) -> ComponentResult<f64> {{ self.component.get_output(name) }}
+    fn step(&mut self, dt: f64) -> ComponentResult<()> {{ self.component.step(dt) }}
+    fn reset(&mut self) -> ComponentResult<()> {{ self.component.reset() }}
+    fn set_parameter(&mut self, name: &str, value: f64) -> ComponentResult<()> {{ self.component.set_parameter(name, value) }}
+    fn get_all_outputs(&self) -> HashMap<String, f64> {{ self.component.get_all_outputs() }}
+    fn metadata(&self) -> ComponentMetadata {{ self.component.metadata() }}
+    fn solver_calls(&self) -> Option<u64> {{ self.component.solver_calls() }}
+}}", name = name).unwrap();
+}
+
+#[cfg(feature = "openmodelica")]
+fn build_openmodelica() {
+    // Locate the OpenModelica installation
+    let install = find_openmodelica();
+    let omc_include = install.include.to_string_lossy().into_owned();
+    let omc_gc_include = install.gc_include.to_string_lossy().into_owned();
+    println!("cargo:warning=Using OpenModelica from {} ({})", install.root_description, install.source);
+    
+    // Add all library search paths
+    for path in &install.lib_dirs {
+        println!("cargo:rustc-link-search=native={}", path.display());
+        println!("cargo:warning=Added library search path: {}", path.display());
+    }
     
     // Link libraries
     println!("cargo:rustc-link-lib=dylib=SimulationRuntimeC");
     println!("cargo:rustc-link-lib=dylib=OpenModelicaRuntimeC");
     println!("cargo:rustc-link-lib=dylib=omcgc");
     
-    if cfg!(target_os = "linux") {
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("linux") {
         println!("cargo:rustc-link-lib=lapack");
         println!("cargo:rustc-link-lib=blas");
     } else {
@@ -55,18 +320,430 @@ fn main() {
     
     println!("cargo:warning=Using OpenModelica libraries");
     
+    // setjmp guard for calls into model code
+    compile_guard(&omc_include, &omc_gc_include);
+    
     let modelica_core = PathBuf::from("space-colony-modelica-core");
+
+    // Translate .mo sources that changed since the last build
+    #[cfg(feature = "compile-modelica")]
+    compile_modelica_sources(&modelica_core, &install.omc);
+
+    // Find every compiled model
+    let components = discover_components(&modelica_core);
+    if components.is_empty() {
+        panic!(
+            "No compiled Modelica components found in {}\n\
+             Please run: cd space-colony-modelica-core && ./scripts/build_all.sh",
+            modelica_core.join("build").display()
+        );
+    }
+
+    for component_name in &components {
+        // Compile components
+        compile_component(&modelica_core, component_name, &omc_include, &omc_gc_include);
+
+        // Generate bindings
+        generate_bindings(&modelica_core, component_name, &omc_include, &omc_gc_include);
+    }
+
+    generate_components_index(&components);
+}
+
+/// Compiles `src/runtime/mmc_guard.c`, which catches the runtime's longjmp()s,
+/// `src/runtime/mmc_messages.c`, which forwards its messages to Rust, and the
+/// stub model functions the guard's unit tests call
+#[cfg(feature = "openmodelica")]
+fn compile_guard(omc_include: &str, omc_gc_include: &str) {
+    println!("cargo:rerun-if-changed=src/runtime/mmc_guard.c");
+    println!("cargo:rerun-if-changed=src/runtime/mmc_messages.c");
+    println!("cargo:rerun-if-changed=src/runtime/mmc_guard_test.c");
+    
+    cc::Build::new()
+        .file("src/runtime/mmc_guard.c")
+        .file("src/runtime/mmc_messages.c")
+        .file("src/runtime/mmc_guard_test.c")
+        .include(omc_include)
+        .include(omc_gc_include)
+        .warnings(false)
+        .compile("mrf_mmc_guard");
+}
+
+/// Where the OpenModelica headers and runtime libraries live
+#[cfg(feature = "openmodelica")]
+struct OmcInstall {
+    include: PathBuf,
+    gc_include: PathBuf,
+    lib_dirs: Vec<PathBuf>,
+    root_description: String,
+    /// The omc compiler, used by the `compile-modelica` feature
+    #[cfg_attr(not(feature = "compile-modelica"), allow(dead_code))]
+    omc: PathBuf,
+    /// How the installation was found, for the build log
+    source: &'static str,
+}
+
+/// Finds the OpenModelica installation
+///
+/// Probed in order:
+/// 1. `OMC_INCLUDE_DIR` / `OMC_LIB_DIR` (explicit overrides, both required)
+/// 2. `OPENMODELICAHOME`
+/// 3. `pkg-config` (packages `openmodelica` and `omc`)
+/// 4. `omc --getInstallationDirectoryPath` (`OMC` selects the executable)
+/// 5. Platform default locations
+///
+/// Panics with every location probed if none is usable.
+#[cfg(feature = "openmodelica")]
+fn find_openmodelica() -> OmcInstall {
+    for var in ["OMC_INCLUDE_DIR", "OMC_LIB_DIR", "OPENMODELICAHOME", "OMC", "PKG_CONFIG"] {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
+    
+    let mut probed: Vec<String> = Vec::new();
+    
+    // 1. Explicit overrides
+    match (env::var_os("OMC_INCLUDE_DIR"), env::var_os("OMC_LIB_DIR")) {
+        (Some(include), Some(lib)) => {
+            let include = PathBuf::from(include);
+            let lib = PathBuf::from(lib);
+            match check_install(&include, std::slice::from_ref(&lib)) {
+                Ok((c, gc, lib_dirs)) => return OmcInstall {
+                    include: c,
+                    gc_include: gc,
+                    lib_dirs,
+                    omc: omc_executable(None),
+                    root_description: format!("{} and {}", include.display(), lib.display()),
+                    source: "OMC_INCLUDE_DIR/OMC_LIB_DIR",
+                },
+                Err(why) => panic!(
+                    "OMC_INCLUDE_DIR/OMC_LIB_DIR are set but not usable: {}\n\
+                     OMC_INCLUDE_DIR must contain c/openmodelica.h and gc/, \
+                     OMC_LIB_DIR must contain libSimulationRuntimeC",
+                    why
+                ),
+            }
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            panic!("OMC_INCLUDE_DIR and OMC_LIB_DIR must be set together");
+        }
+        (None, None) => probed.push("OMC_INCLUDE_DIR/OMC_LIB_DIR: not set".to_string()),
+    }
     
-    // Compile components
-    compile_component(&modelica_core, "SimpleThermalMVP", &omc_include, &omc_gc_include);
+    // 2-5. Installation roots
+    let mut roots: Vec<(PathBuf, &'static str)> = Vec::new();
+    match env::var_os("OPENMODELICAHOME") {
+        Some(home) => roots.push((PathBuf::from(home), "OPENMODELICAHOME")),
+        None => probed.push("OPENMODELICAHOME: not set".to_string()),
+    }
+    match pkg_config_prefix() {
+        Ok(prefix) => roots.push((prefix, "pkg-config")),
+        Err(why) => probed.push(format!("pkg-config: {}", why)),
+    }
+    match omc_installation_directory() {
+        Ok(dir) => roots.push((dir, "omc --getInstallationDirectoryPath")),
+        Err(why) => probed.push(format!("omc --getInstallationDirectoryPath: {}", why)),
+    }
+    for default in default_roots() {
+        roots.push((default, "default location"));
+    }
+    
+    for (root, source) in roots {
+        let include = root.join("include").join("omc");
+        match check_install(&include, &lib_candidates(&root)) {
+            Ok((c, gc, lib_dirs)) => return OmcInstall {
+                include: c,
+                gc_include: gc,
+                lib_dirs,
+                omc: omc_executable(Some(&root)),
+                root_description: root.display().to_string(),
+                source,
+            },
+            Err(why) => probed.push(format!("{} ({}): {}", root.display(), source, why)),
+        }
+    }
+    
+    panic!(
+        "OpenModelica installation not found. Probed:\n  - {}\n\n\
+         Install OpenModelica, or point the build at it with OPENMODELICAHOME=<prefix>, \
+         or with OMC_INCLUDE_DIR=<prefix>/include/omc and OMC_LIB_DIR=<prefix>/lib/omc.\n\
+         To build without OpenModelica, disable the `openmodelica` feature.",
+        probed.join("\n  - ")
+    );
+}
+
+/// Checks an `include/omc` directory and candidate library directories
+///
+/// Returns the C and GC include directories and the library directories
+/// that exist, or why the location is not usable.
+#[cfg(feature = "openmodelica")]
+fn check_install(include: &Path, lib_candidates: &[PathBuf]) -> Result<(PathBuf, PathBuf, Vec<PathBuf>), String> {
+    let c = include.join("c");
+    let gc = include.join("gc");
+    if !c.join("openmodelica.h").exists() {
+        return Err(format!("{} not found", c.join("openmodelica.h").display()));
+    }
+    if !gc.exists() {
+        return Err(format!("{} not found", gc.display()));
+    }
     
-    // Generate bindings
-    generate_bindings(&modelica_core, "SimpleThermalMVP", &omc_include, &omc_gc_include);
+    let lib_dirs: Vec<PathBuf> = lib_candidates.iter().filter(|dir| dir.exists()).cloned().collect();
+    let has_runtime = lib_dirs.iter().any(|dir| {
+        ["so", "dylib", "a", "dll.a", "lib"].iter()
+            .any(|ext| dir.join(format!("libSimulationRuntimeC.{}", ext)).exists()
+                || dir.join(format!("SimulationRuntimeC.{}", ext)).exists())
+    });
+    if !has_runtime {
+        return Err(format!(
+            "libSimulationRuntimeC not found in {}",
+            lib_candidates.iter().map(|d| d.display().to_string()).collect::<Vec<_>>().join(", ")
+        ));
+    }
+    
+    Ok((c, gc, lib_dirs))
+}
+
+/// Library directories to try under an installation root
+#[cfg(feature = "openmodelica")]
+fn lib_candidates(root: &Path) -> Vec<PathBuf> {
+    let arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_else(|_| "x86_64".to_string());
+    let lib = root.join("lib");
+    vec![
+        lib.join("omc"),
+        lib.join(format!("{}-linux-gnu", arch)).join("omc"),
+        lib.clone(),
+        lib.join(format!("{}-linux-gnu", arch)),
+    ]
+}
+
+/// Installation roots tried when nothing else is configured
+#[cfg(feature = "openmodelica")]
+fn default_roots() -> Vec<PathBuf> {
+    match env::var("CARGO_CFG_TARGET_OS").as_deref() {
+        Ok("macos") => vec![
+            PathBuf::from("/Applications/OpenModelica/build_cmake/install_cmake"),
+            PathBuf::from("/opt/openmodelica"),
+            PathBuf::from("/usr/local"),
+        ],
+        Ok("windows") => vec![PathBuf::from("C:\\Program Files\\OpenModelica")],
+        _ => vec![
+            PathBuf::from("/usr"),
+            PathBuf::from("/usr/local"),
+            PathBuf::from("/opt/openmodelica"),
+        ],
+    }
+}
+
+/// Asks pkg-config for the OpenModelica prefix
+#[cfg(feature = "openmodelica")]
+fn pkg_config_prefix() -> Result<PathBuf, String> {
+    let pkg_config = env::var("PKG_CONFIG").unwrap_or_else(|_| "pkg-config".to_string());
+    for package in ["openmodelica", "omc"] {
+        let output = Command::new(&pkg_config)
+            .args(["--variable=prefix", package])
+            .output()
+            .map_err(|e| format!("could not run {}: {}", pkg_config, e))?;
+        let prefix = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !prefix.is_empty() {
+            return Ok(PathBuf::from(prefix));
+        }
+    }
+    Err("no 'openmodelica' or 'omc' package".to_string())
+}
+
+/// The omc executable: `OMC`, else `<root>/bin/omc`, else `omc` on `PATH`
+#[cfg(feature = "openmodelica")]
+fn omc_executable(root: Option<&Path>) -> PathBuf {
+    if let Some(omc) = env::var_os("OMC") {
+        return PathBuf::from(omc);
+    }
+    root.map(|root| root.join("bin").join("omc"))
+        .filter(|omc| omc.exists())
+        .unwrap_or_else(|| PathBuf::from("omc"))
+}
+
+/// Asks the omc compiler where it is installed
+#[cfg(feature = "openmodelica")]
+fn omc_installation_directory() -> Result<PathBuf, String> {
+    let omc = env::var("OMC").unwrap_or_else(|_| "omc".to_string());
+    let output = Command::new(&omc)
+        .arg("--getInstallationDirectoryPath")
+        .output()
+        .map_err(|e| format!("could not run {}: {}", omc, e))?;
+    let dir = String::from_utf8_lossy(&output.stdout).trim().trim_matches('"').to_string();
+    if !output.status.success() || dir.is_empty() {
+        return Err(format!("{} did not report an installation directory", omc));
+    }
+    Ok(PathBuf::from(dir))
+}
+
+/// An entry of `components.txt`
+///
+/// Each line is a model name, optionally followed by `:` and the `.mo`
+/// files it is built from, relative to `space-colony-modelica-core`:
+///
+/// ```text
+/// SimpleThermalMVP: models/SimpleThermalMVP.mo
+/// SolarPanel        # already compiled by scripts/build_component.sh
+/// ```
+#[cfg(feature = "openmodelica")]
+struct ManifestEntry {
+    name: String,
+    sources: Vec<PathBuf>,
+}
+
+/// Reads `components.txt`, or returns `None` if there is no manifest
+#[cfg(feature = "openmodelica")]
+fn read_manifest(modelica_core: &Path) -> Option<Vec<ManifestEntry>> {
+    let manifest = modelica_core.join("components.txt");
+    println!("cargo:rerun-if-changed={}", manifest.display());
+    if !manifest.exists() {
+        return None;
+    }
+
+    println!("cargo:warning=Reading component list from {}", manifest.display());
+    let entries = std::fs::read_to_string(&manifest)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", manifest.display(), e))
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (name, sources) = line.split_once(':').unwrap_or((line, ""));
+            ManifestEntry {
+                name: name.trim().to_string(),
+                sources: sources.split_whitespace().map(|s| modelica_core.join(s)).collect(),
+            }
+        })
+        .collect();
+    Some(entries)
+}
+
+/// Lists the models to build
+///
+/// If `space-colony-modelica-core/components.txt` exists it is used as the
+/// list. Otherwise every directory `build/<Name>/` containing `<Name>.c`
+/// is taken.
+#[cfg(feature = "openmodelica")]
+fn discover_components(modelica_core: &Path) -> Vec<String> {
+    let build_dir = modelica_core.join("build");
+    println!("cargo:rerun-if-changed={}", build_dir.display());
+
+    let mut components: Vec<String> = if let Some(entries) = read_manifest(modelica_core) {
+        entries.into_iter().map(|entry| entry.name).collect()
+    } else {
+        std::fs::read_dir(&build_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| {
+                        let path = entry.ok()?.path();
+                        let name = path.file_name()?.to_str()?.to_string();
+                        path.join(format!("{}.c", name)).exists().then_some(name)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    components.sort();
+    components.dedup();
+    println!("cargo:warning=Found {} component(s): {}", components.len(), components.join(", "));
+    components
+}
+
+/// Translates the `.mo` sources listed in `components.txt` to C with omc
+///
+/// Output goes to `build/<Name>/`, the same place
+/// `scripts/build_component.sh` writes to. A hash of the sources is stored
+/// next to it so omc only runs again when the Modelica code changes.
+#[cfg(feature = "compile-modelica")]
+fn compile_modelica_sources(modelica_core: &Path, omc: &Path) {
+    let Some(entries) = read_manifest(modelica_core) else {
+        println!("cargo:warning=No components.txt, skipping .mo compilation");
+        return;
+    };
+
+    for entry in entries.iter().filter(|e| !e.sources.is_empty()) {
+        let mut hash = Fnv64::new();
+        hash.write(entry.name.as_bytes());
+        for source in &entry.sources {
+            println!("cargo:rerun-if-changed={}", source.display());
+            let text = std::fs::read(source)
+                .unwrap_or_else(|e| panic!("Failed to read {}: {}", source.display(), e));
+            hash.write(&text);
+        }
+        let hash = format!("{:016x}", hash.finish());
+
+        let out_dir = modelica_core.join("build").join(&entry.name);
+        let hash_file = out_dir.join(".source-hash");
+        let main_c = out_dir.join(format!("{}.c", entry.name));
+        if main_c.exists() && std::fs::read_to_string(&hash_file).ok().as_deref() == Some(hash.as_str()) {
+            println!("cargo:warning={} is up to date", entry.name);
+            continue;
+        }
+
+        println!("cargo:warning=Translating {} with {}", entry.name, omc.display());
+        std::fs::create_dir_all(&out_dir)
+            .unwrap_or_else(|e| panic!("Failed to create {}: {}", out_dir.display(), e));
+
+        let mut script = String::new();
+        for source in &entry.sources {
+            let source = source.canonicalize()
+                .unwrap_or_else(|e| panic!("Failed to resolve {}: {}", source.display(), e));
+            let source = source.to_string_lossy().replace('\\', "/");
+            script.push_str(&format!("loadFile(\"{}\"); getErrorString();\n", source));
+        }
+        script.push_str(&format!(
+            "translateModel({name}, fileNamePrefix=\"{name}\"); getErrorString();\n",
+            name = entry.name
+        ));
+        let script_file = out_dir.join(format!("{}.mos", entry.name));
+        std::fs::write(&script_file, script)
+            .unwrap_or_else(|e| panic!("Failed to write {}: {}", script_file.display(), e));
+
+        let output = Command::new(omc)
+            .arg(script_file.file_name().unwrap())
+            .current_dir(&out_dir)
+            .output()
+            .unwrap_or_else(|e| panic!("Failed to run {}: {}", omc.display(), e));
+        if !output.status.success() || !main_c.exists() {
+            panic!(
+                "omc failed to translate {}:\n{}{}",
+                entry.name,
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        std::fs::write(&hash_file, hash)
+            .unwrap_or_else(|e| panic!("Failed to write {}: {}", hash_file.display(), e));
+        println!("cargo:warning=  ✓ Generated C code in {}", out_dir.display());
+    }
 }
 
-// ... rest stays the same
+/// FNV-1a, stable across toolchains unlike `DefaultHasher`
+#[cfg(feature = "compile-modelica")]
+struct Fnv64(u64);
+
+#[cfg(feature = "compile-modelica")]
+impl Fnv64 {
+    fn new() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(feature = "openmodelica")]
 fn compile_component(
-    modelica_core: &PathBuf, 
+    modelica_core: &Path, 
     component_name: &str,
     omc_include: &str,
     omc_gc_include: &str
@@ -87,7 +764,7 @@ fn compile_component(
     println!("cargo:warning=Compiling component: {}", component_name);
     
     let c_files: Vec<PathBuf> = std::fs::read_dir(&component_dir)
-        .expect(&format!("Failed to read {}", component_dir.display()))
+        .unwrap_or_else(|_| panic!("Failed to read {}", component_dir.display()))
         .filter_map(|entry| {
             let entry = entry.ok()?;
             let path = entry.path();
@@ -112,18 +789,91 @@ fn compile_component(
         .include(omc_include)
         .include(omc_gc_include)
         .define("OPENMODELICA_XML_FROM_FILE_AT_RUNTIME", None)
+        // Keep each model's helpers and globals out of the shared namespace
+        .flag_if_supported("-fvisibility=hidden")
+        .flag_if_supported("-fno-common")
         .warnings(false);
     
     for file in c_files {
         build.file(file);
     }
     
-    build.compile(&format!("component_{}", component_name.to_lowercase()));
+    let objects = build.compile_intermediates();
+    let lib_name = format!("component_{}", component_name.to_lowercase());
+    let isolated = isolate_symbols(&build, component_name, &objects);
+    archive(&build, &lib_name, &isolated);
     println!("cargo:warning=  ✓ Compiled successfully");
 }
 
+/// Links a model's objects into one relocatable object in which only
+/// `<Model>_*` symbols stay global
+///
+/// OpenModelica emits many unprefixed globals (`_OMC_LIT*`, `omc_*` helper
+/// functions, ...) that collide when two models are linked into one
+/// binary. Everything the model defines except its prefixed entry points
+/// is made local, so each model keeps its own private copies.
+#[cfg(feature = "openmodelica")]
+fn isolate_symbols(build: &cc::Build, component_name: &str, objects: &[PathBuf]) -> PathBuf {
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let combined = out_path.join(format!("{}_combined.o", component_name.to_lowercase()));
+    let isolated = out_path.join(format!("{}_isolated.o", component_name.to_lowercase()));
+    
+    // Partial link with the C compiler driver so cross toolchains work
+    let mut link = build.get_compiler().to_command();
+    link.arg("-r").arg("-nostdlib").arg("-o").arg(&combined).args(objects);
+    
+    // Pick the linker flavour by target OS, not the build host's, since the
+    // partial link runs with the target's toolchain
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("macos") {
+        // ld64 hides everything not exported during the partial link
+        link.arg(format!("-Wl,-exported_symbol,_{}_*", component_name));
+        run(link, "partial link");
+        return combined;
+    }
+    run(link, "partial link");
+    
+    let objcopy = env::var("OBJCOPY").unwrap_or_else(|_| "objcopy".to_string());
+    println!("cargo:rerun-if-env-changed=OBJCOPY");
+    let mut localize = Command::new(objcopy);
+    localize
+        .arg("--wildcard")
+        .arg(format!("--keep-global-symbol={}_*", component_name))
+        .arg(&combined)
+        .arg(&isolated);
+    run(localize, "symbol localization");
+    
+    println!("cargo:warning=  Isolated symbols: only {}_* remain global", component_name);
+    isolated
+}
+
+/// Packs an object into `lib<lib_name>.a` and tells cargo to link it
+#[cfg(feature = "openmodelica")]
+fn archive(build: &cc::Build, lib_name: &str, object: &Path) {
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let lib_file = out_path.join(format!("lib{}.a", lib_name));
+    let _ = std::fs::remove_file(&lib_file);
+    
+    let mut ar = build.get_archiver();
+    ar.arg("crs").arg(&lib_file).arg(object);
+    run(ar, "archive");
+    
+    println!("cargo:rustc-link-search=native={}", out_path.display());
+    println!("cargo:rustc-link-lib=static={}", lib_name);
+}
+
+#[cfg(feature = "openmodelica")]
+fn run(mut command: Command, what: &str) {
+    let status = command
+        .status()
+        .unwrap_or_else(|e| panic!("Failed to run {} ({:?}): {}", what, command, e));
+    if !status.success() {
+        panic!("{} failed ({:?}): {}", what, command, status);
+    }
+}
+
+#[cfg(feature = "openmodelica")]
 fn generate_bindings(
-    modelica_core: &PathBuf, 
+    modelica_core: &Path, 
     component_name: &str,
     omc_include: &str,
     omc_gc_include: &str
@@ -173,4 +923,36 @@ fn generate_bindings(
         .expect("Couldn't write bindings!");
     
     println!("cargo:warning=✓ Bindings generated successfully");
-}
\ No newline at end of file
+}
+
+/// Writes `components_index.rs`: one module per model wrapping its
+/// bindings, plus the list of compiled model names
+#[cfg(feature = "openmodelica")]
+fn generate_components_index(components: &[String]) {
+    let mut index = String::from("// Generated by build.rs, do not edit\n\n");
+
+    index.push_str("/// Names of all models compiled into this crate\n");
+    index.push_str("pub const COMPILED_MODELS: &[&str] = &[\n");
+    for component_name in components {
+        index.push_str(&format!("    \"{}\",\n", component_name));
+    }
+    index.push_str("];\n");
+
+    for component_name in components {
+        let module = component_name.to_lowercase();
+        index.push_str(&format!(
+            "\n/// Raw OpenModelica bindings for {name}\n\
+             pub mod {module} {{\n    \
+                 pub const MODEL_NAME: &str = \"{name}\";\n    \
+                 include!(concat!(env!(\"OUT_DIR\"), \"/{module}_bindings.rs\"));\n\
+             }}\n",
+            name = component_name,
+            module = module,
+        ));
+    }
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let index_file = out_path.join("components_index.rs");
+    std::fs::write(&index_file, index).expect("Couldn't write components index!");
+    println!("cargo:warning=✓ Components index written to {}", index_file.display());
+}
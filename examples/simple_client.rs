@@ -0,0 +1,30 @@
+//! Heats a habitat for a minute and prints its temperature every 10 s
+//!
+//! Run with `cargo run --example simple_client`.
+
+use modelica_rust_ffi::{ComponentRegistry, SimpleThermalComponent, SimulationComponent};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut thermal = SimpleThermalComponent::new()?;
+    thermal.initialize()?;
+    thermal.set_bool_input("heaterOn", true)?;
+
+    let mut registry = ComponentRegistry::new();
+    registry.add("habitat_1".to_string(), Box::new(thermal))?;
+
+    let dt = 0.1;
+    for step in 1..=600 {
+        registry.step_all(dt)?;
+
+        if step % 100 == 0 {
+            let habitat = registry.get_by_name("habitat_1").unwrap();
+            println!(
+                "t = {:>4.0} s  temperature = {:.2} K",
+                step as f64 * dt,
+                habitat.get_output("temperature")?
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+/* Generated by cbindgen from src/ffi.rs - do not edit */
+
+#ifndef MODELICA_RUST_FFI_H
+#define MODELICA_RUST_FFI_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+// Result of a C API call
+typedef enum MrfStatus {
+  MRF_STATUS_OK = 0,
+  // A required pointer argument was NULL
+  MRF_STATUS_NULL_POINTER = 1,
+  // A string argument was not valid UTF-8
+  MRF_STATUS_INVALID_STRING = 2,
+  // Unknown component, component type or variable
+  MRF_STATUS_NOT_FOUND = 3,
+  // Rejected value (type, bounds, causality or fixed parameter)
+  MRF_STATUS_INVALID_INPUT = 4,
+  // A component failed to step
+  MRF_STATUS_STEP_FAILED = 5,
+  // Any other component error
+  MRF_STATUS_ERROR = 6,
+  // The call panicked; the registry may be inconsistent
+  MRF_STATUS_PANIC = 7,
+} MrfStatus;
+
+// Opaque registry handle
+typedef struct MrfRegistry MrfRegistry;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates an empty registry that can instantiate every built-in model
+//
+// Release it with `mrf_registry_free`.
+struct MrfRegistry *mrf_registry_new(void);
+
+// Destroys a registry and every component in it
+//
+// # Safety
+//
+// `registry` must be NULL or a handle from `mrf_registry_new` that has not
+// been freed yet.
+void mrf_registry_free(struct MrfRegistry *registry);
+
+// Creates and initializes a component of `type_name` called `name`
+//
+// # Safety
+//
+// `registry` must be a live handle; `type_name` and `name` must be
+// NUL-terminated strings.
+enum MrfStatus mrf_registry_add(struct MrfRegistry *registry,
+                                const char *type_name,
+                                const char *name);
+
+// Like `mrf_registry_add`, with `count` parameter overrides applied before
+// initialization (so fixed parameters can be set too)
+//
+// # Safety
+//
+// As `mrf_registry_add`; in addition `parameter_names` and `values` must
+// point to `count` elements each (they may be NULL when `count` is 0).
+enum MrfStatus mrf_registry_add_with_parameters(struct MrfRegistry *registry,
+                                                const char *type_name,
+                                                const char *name,
+                                                const char *const *parameter_names,
+                                                const double *values,
+                                                size_t count);
+
+// Removes and destroys a component
+//
+// # Safety
+//
+// `registry` must be a live handle; `name` a NUL-terminated string.
+enum MrfStatus mrf_registry_remove(struct MrfRegistry *registry, const char *name);
+
+// Number of components in the registry (0 for NULL)
+//
+// # Safety
+//
+// `registry` must be NULL or a live handle.
+size_t mrf_registry_len(const struct MrfRegistry *registry);
+
+// Advances every component by `dt` seconds
+//
+// # Safety
+//
+// `registry` must be a live handle.
+enum MrfStatus mrf_registry_step_all(struct MrfRegistry *registry, double dt);
+
+// Sets a real input (or state) of a component
+//
+// # Safety
+//
+// `registry` must be a live handle; `component` and `variable` must be
+// NUL-terminated strings.
+enum MrfStatus mrf_component_set_real(struct MrfRegistry *registry,
+                                      const char *component,
+                                      const char *variable,
+                                      double value);
+
+// Sets a boolean input of a component
+//
+// # Safety
+//
+// As `mrf_component_set_real`.
+enum MrfStatus mrf_component_set_bool(struct MrfRegistry *registry,
+                                      const char *component,
+                                      const char *variable,
+                                      bool value);
+
+// Changes a parameter of a component
+//
+// # Safety
+//
+// As `mrf_component_set_real`.
+enum MrfStatus mrf_component_set_parameter(struct MrfRegistry *registry,
+                                           const char *component,
+                                           const char *parameter,
+                                           double value);
+
+// Reads any variable of a component into `out` (booleans read as 0/1)
+//
+// # Safety
+//
+// As `mrf_component_set_real`; `out` must be valid for writing a double.
+enum MrfStatus mrf_component_get_real(struct MrfRegistry *registry,
+                                      const char *component,
+                                      const char *variable,
+                                      double *out);
+
+// Resets a component to its start values
+//
+// # Safety
+//
+// `registry` must be a live handle; `component` a NUL-terminated string.
+enum MrfStatus mrf_component_reset(struct MrfRegistry *registry, const char *component);
+
+// Message for the last failed call on this thread, or NULL
+//
+// The string stays valid until the next failing call on the same thread.
+const char *mrf_last_error(void);
+
+// Crate version as a static string
+const char *mrf_version(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MODELICA_RUST_FFI_H */
@@ -0,0 +1,590 @@
+//! `#[derive(SimulationComponent)]` for models written directly in Rust
+//!
+//! Annotate the fields that make up the model's variable table and the
+//! derive generates the whole `SimulationComponent` implementation:
+//! get/set by name with type and bounds checks, metadata, reset to start
+//! values, and explicit Euler integration of the states.
+//!
+//! ```ignore
+//! use modelica_rust_ffi::SimulationComponent;
+//!
+//! #[derive(SimulationComponent)]
+//! #[component(category = "Thermal", update = update_outputs)]
+//! struct Room {
+//!     /// Heat capacity of the room
+//!     #[parameter(unit = "J/K", min = 0.0, start = 1000.0)]
+//!     capacity: f64,
+//!
+//!     /// Heater control signal
+//!     #[input(name = "heaterOn")]
+//!     heater_on: bool,
+//!
+//!     /// Room temperature
+//!     #[state(unit = "K", start = 250.0, der = temperature_derivative)]
+//!     temperature: f64,
+//!
+//!     #[output(unit = "W")]
+//!     heat_flow: f64,
+//! }
+//!
+//! impl Room {
+//!     fn temperature_derivative(&self) -> f64 {
+//!         if self.heater_on { 500.0 / self.capacity } else { 0.0 }
+//!     }
+//!
+//!     fn update_outputs(&mut self) {
+//!         self.heat_flow = if self.heater_on { 500.0 } else { 0.0 };
+//!     }
+//! }
+//! ```
+//!
+//! # Struct attribute
+//!
+//! `#[component(...)]` is optional:
+//!
+//! * `name = "..."` - component type name, defaults to the struct name
+//! * `category = "..."` - metadata category, defaults to `"Native"`
+//! * `update = method` - `fn(&mut self)` called after every step and reset,
+//!   typically to compute outputs from the new state
+//! * `initialized = field` - a `bool` field the derive sets on `initialize()`
+//!   and the first `step()` and clears on `reset()`; required for fixed
+//!   parameters
+//!
+//! # Field attributes
+//!
+//! One of `#[input]`, `#[output]`, `#[parameter]` or `#[state(der = method)]`
+//! per variable. Fields without one are ignored. All accept:
+//!
+//! * `name = "..."` - variable name, defaults to the field name
+//! * `unit = "..."`, `description = "..."` (defaults to the doc comment)
+//! * `min = expr`, `max = expr` - `f64` bounds checked whenever the variable is set
+//! * `start = expr` - value restored by `initialize()` and `reset()`, of the
+//!   field's type; for parameters only reported in the metadata
+//!
+//! Parameters are tunable unless marked `#[parameter(fixed)]`: fixed ones
+//! can only be changed before initialization or after a reset, as in
+//! `ModelicaRuntime`.
+//!
+//! States must be real and name a `fn(&self) -> f64` returning their time
+//! derivative. Fields may be `f64`, `f32`, `bool` or any primitive integer;
+//! integer fields reject values that are not whole or out of the type's range.
+//! On initialize and reset, inputs, outputs and states without a `start`
+//! return to their type's default. Parameters always keep their current
+//! value, so overrides set before `initialize()` take effect.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, LitStr, Path, Type};
+
+#[proc_macro_derive(SimulationComponent, attributes(component, input, output, parameter, state))]
+pub fn derive_simulation_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Role {
+    Input,
+    Output,
+    Parameter,
+    State,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Real,
+    Boolean,
+    Integer,
+}
+
+struct Variable {
+    field: Ident,
+    ty: Type,
+    name: String,
+    role: Role,
+    kind: Kind,
+    unit: Option<LitStr>,
+    description: Option<String>,
+    min: Option<Expr>,
+    max: Option<Expr>,
+    start: Option<Expr>,
+    der: Option<Path>,
+    /// `#[parameter(fixed)]`
+    fixed: bool,
+}
+
+#[derive(Default)]
+struct ComponentAttrs {
+    name: Option<LitStr>,
+    category: Option<LitStr>,
+    update: Option<Path>,
+    initialized: Option<Ident>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let attrs = component_attrs(&input.attrs)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new(input.span(), "SimulationComponent can only be derived for structs with named fields")),
+        },
+        _ => return Err(Error::new(input.span(), "SimulationComponent can only be derived for structs")),
+    };
+
+    let mut variables = Vec::new();
+    for field in fields {
+        if let Some(variable) = parse_variable(field)? {
+            if variables.iter().any(|v: &Variable| v.name == variable.name) {
+                return Err(Error::new(field.span(), format!("duplicate variable name '{}'", variable.name)));
+            }
+            variables.push(variable);
+        }
+    }
+
+    let krate = quote!(::modelica_rust_ffi);
+    let type_name = attrs.name
+        .map(|name| name.value())
+        .unwrap_or_else(|| ident.to_string());
+    let category = attrs.category
+        .map(|category| category.value())
+        .unwrap_or_else(|| "Native".to_string());
+    let update = attrs.update.map(|path| quote!(self.#path();));
+
+    if let Some(fixed) = variables.iter().find(|v| v.fixed) {
+        if attrs.initialized.is_none() {
+            return Err(Error::new(
+                fixed.field.span(),
+                "fixed parameters need #[component(initialized = field)], a bool field tracking initialization",
+            ));
+        }
+    }
+    let set_initialized = attrs.initialized.as_ref().map(|field| quote!(self.#field = true;));
+    let clear_initialized = attrs.initialized.as_ref().map(|field| quote!(self.#field = false;));
+
+    let specs: Vec<TokenStream2> = variables.iter().map(|v| spec(v, &krate)).collect();
+
+    let set_input_arms = variables.iter().map(|v| {
+        let name = &v.name;
+        let body = match (v.role, v.kind) {
+            (Role::Output, _) => computed_error(&krate),
+            (_, Kind::Boolean) => type_error("Boolean", &krate),
+            (Role::Parameter, _) => quote! {
+                <Self as #krate::SimulationComponent>::set_parameter(self, name, value)
+            },
+            _ => assign(v, &krate),
+        };
+        quote!(#name => { #body })
+    });
+
+    let set_bool_input_arms = variables.iter().map(|v| {
+        let name = &v.name;
+        let field = &v.field;
+        let body = match (v.role, v.kind) {
+            (Role::Output, _) => computed_error(&krate),
+            (_, Kind::Boolean) => quote! {
+                self.#field = value;
+                Ok(())
+            },
+            (_, Kind::Real) => type_error("Real", &krate),
+            (_, Kind::Integer) => type_error("Integer", &krate),
+        };
+        quote!(#name => { #body })
+    });
+
+    let get_output_arms = variables.iter().map(|v| {
+        let name = &v.name;
+        let value = read(v);
+        quote!(#name => Ok(#value))
+    });
+
+    let set_parameter_arms = variables.iter()
+        .filter(|v| v.role == Role::Parameter)
+        .map(|v| {
+            let name = &v.name;
+            let field = &v.field;
+            let body = if v.kind == Kind::Boolean {
+                quote! {
+                    self.#field = value != 0.0;
+                    Ok(())
+                }
+            } else {
+                assign(v, &krate)
+            };
+            // Checked above: fixed parameters come with an `initialized` field
+            let frozen = attrs.initialized.as_ref().filter(|_| v.fixed).map(|initialized| quote! {
+                if self.#initialized {
+                    return Err(#krate::ComponentError::FixedParameter(name.to_string()));
+                }
+            });
+            quote!(#name => { #frozen #body })
+        });
+
+    let outputs = variables.iter()
+        .filter(|v| v.role == Role::Output)
+        .map(|v| {
+            let name = &v.name;
+            let value = read(v);
+            quote!(outputs.insert(#name.to_string(), #value);)
+        });
+
+    // Parameters keep their value, as in `ModelicaRuntime`
+    let resets = variables.iter().filter(|v| v.role != Role::Parameter).map(|v| {
+        let field = &v.field;
+        let ty = &v.ty;
+        match &v.start {
+            Some(start) => quote!(self.#field = #start;),
+            None => quote!(self.#field = <#ty as ::core::default::Default>::default();),
+        }
+    });
+
+    let states: Vec<&Variable> = variables.iter().filter(|v| v.role == Role::State).collect();
+    let derivative_idents: Vec<Ident> = states.iter()
+        .map(|v| Ident::new(&format!("__der_{}", v.field), v.field.span()))
+        .collect();
+    let derivatives = states.iter().zip(&derivative_idents).map(|(v, der_ident)| {
+        let der = v.der.as_ref().expect("states are checked to have a derivative");
+        quote!(let #der_ident: f64 = self.#der();)
+    });
+    let integrations = states.iter().zip(&derivative_idents).map(|(v, der_ident)| {
+        let field = &v.field;
+        let ty = &v.ty;
+        if is_type(ty, "f64") {
+            quote!(self.#field += dt * #der_ident;)
+        } else {
+            quote!(self.#field += (dt * #der_ident) as #ty;)
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::SimulationComponent for #ident #ty_generics #where_clause {
+            fn component_type(&self) -> &str {
+                #type_name
+            }
+
+            fn initialize(&mut self) -> #krate::ComponentResult<()> {
+                <Self as #krate::SimulationComponent>::reset(self)?;
+                #set_initialized
+                Ok(())
+            }
+
+            fn set_input(&mut self, name: &str, value: f64) -> #krate::ComponentResult<()> {
+                match name {
+                    #(#set_input_arms)*
+                    _ => Err(#krate::ComponentError::VariableNotFound(name.to_string())),
+                }
+            }
+
+            fn set_bool_input(&mut self, name: &str, value: bool) -> #krate::ComponentResult<()> {
+                match name {
+                    #(#set_bool_input_arms)*
+                    _ => Err(#krate::ComponentError::VariableNotFound(name.to_string())),
+                }
+            }
+
+            fn get_output(&self, name: &str) -> #krate::ComponentResult<f64> {
+                match name {
+                    #(#get_output_arms,)*
+                    _ => Err(#krate::ComponentError::VariableNotFound(name.to_string())),
+                }
+            }
+
+            fn step(&mut self, dt: f64) -> #krate::ComponentResult<()> {
+                if dt <= 0.0 || !dt.is_finite() {
+                    return Err(#krate::ComponentError::StepFailed(
+                        format!("Invalid timestep: {}. Must be positive and finite.", dt)
+                    ));
+                }
+
+                // Fixed parameters are frozen from the first step on
+                #set_initialized
+
+                // Evaluate every derivative before moving any state
+                #(#derivatives)*
+                #(#integrations)*
+                #update
+                Ok(())
+            }
+
+            fn reset(&mut self) -> #krate::ComponentResult<()> {
+                #(#resets)*
+                #clear_initialized
+                #update
+                Ok(())
+            }
+
+            fn set_parameter(&mut self, name: &str, value: f64) -> #krate::ComponentResult<()> {
+                match name {
+                    #(#set_parameter_arms)*
+                    _ => Err(#krate::ComponentError::VariableNotFound(name.to_string())),
+                }
+            }
+
+            fn get_all_outputs(&self) -> ::std::collections::HashMap<String, f64> {
+                #[allow(unused_mut)]
+                let mut outputs = ::std::collections::HashMap::new();
+                #(#outputs)*
+                outputs
+            }
+
+            fn metadata(&self) -> #krate::ComponentMetadata {
+                #krate::ComponentMetadata::from_variables(#type_name, #category, &[#(#specs),*])
+            }
+        }
+    })
+}
+
+/// Parses the optional struct-level `#[component(...)]`
+fn component_attrs(attrs: &[Attribute]) -> syn::Result<ComponentAttrs> {
+    let mut result = ComponentAttrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("component")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                result.name = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("category") {
+                result.category = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("update") {
+                result.update = Some(parse_path(meta.value()?)?);
+            } else if meta.path.is_ident("initialized") {
+                result.initialized = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `name`, `category`, `update` or `initialized`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(result)
+}
+
+/// Parses a field's role attribute, if it has one
+fn parse_variable(field: &syn::Field) -> syn::Result<Option<Variable>> {
+    let roles = [
+        ("input", Role::Input),
+        ("output", Role::Output),
+        ("parameter", Role::Parameter),
+        ("state", Role::State),
+    ];
+
+    let mut found: Option<(&Attribute, Role)> = None;
+    for attr in &field.attrs {
+        if let Some((_, role)) = roles.iter().find(|(name, _)| attr.path().is_ident(name)) {
+            if found.is_some() {
+                return Err(Error::new(attr.span(), "a field can have only one of #[input], #[output], #[parameter] or #[state]"));
+            }
+            found = Some((attr, *role));
+        }
+    }
+    let Some((attr, role)) = found else {
+        return Ok(None);
+    };
+
+    let field_ident = field.ident.clone().expect("named fields have identifiers");
+    let mut variable = Variable {
+        name: field_ident.to_string(),
+        field: field_ident,
+        ty: field.ty.clone(),
+        role,
+        kind: kind_of(&field.ty)?,
+        unit: None,
+        description: doc_comment(&field.attrs),
+        min: None,
+        max: None,
+        start: None,
+        der: None,
+        fixed: false,
+    };
+
+    // Bare `#[input]` has no argument list
+    if !matches!(attr.meta, syn::Meta::Path(_)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                variable.name = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("unit") {
+                variable.unit = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("description") {
+                variable.description = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("min") {
+                variable.min = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("max") {
+                variable.max = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("start") {
+                variable.start = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("der") && role == Role::State {
+                variable.der = Some(parse_path(meta.value()?)?);
+            } else if meta.path.is_ident("fixed") && role == Role::Parameter {
+                variable.fixed = true;
+            } else {
+                return Err(meta.error("unknown variable attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    if role == Role::State {
+        if variable.kind != Kind::Real {
+            return Err(Error::new(field.ty.span(), "states must be f64 or f32"));
+        }
+        if variable.der.is_none() {
+            return Err(Error::new(attr.span(), "states need a derivative: #[state(der = method)]"));
+        }
+    }
+
+    Ok(Some(variable))
+}
+
+/// Accepts both `key = method` and `key = "method"`
+fn parse_path(input: syn::parse::ParseStream) -> syn::Result<Path> {
+    if input.peek(LitStr) {
+        input.parse::<LitStr>()?.parse()
+    } else {
+        input.parse()
+    }
+}
+
+fn kind_of(ty: &Type) -> syn::Result<Kind> {
+    const INTEGERS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize",
+        "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+
+    if is_type(ty, "f64") || is_type(ty, "f32") {
+        Ok(Kind::Real)
+    } else if is_type(ty, "bool") {
+        Ok(Kind::Boolean)
+    } else if INTEGERS.iter().any(|name| is_type(ty, name)) {
+        Ok(Kind::Integer)
+    } else {
+        Err(Error::new(ty.span(), "unsupported variable type; expected f64, f32, bool or a primitive integer"))
+    }
+}
+
+fn is_type(ty: &Type, name: &str) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident(name))
+}
+
+/// Joins `///` lines into a single description
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs.iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" "))
+    }
+}
+
+/// The field's value as `f64`
+fn read(v: &Variable) -> TokenStream2 {
+    let field = &v.field;
+    match v.kind {
+        Kind::Boolean => quote!(if self.#field { 1.0 } else { 0.0 }),
+        Kind::Real if is_type(&v.ty, "f64") => quote!(self.#field),
+        _ => quote!(self.#field as f64),
+    }
+}
+
+/// Checks and stores a real `value` in the field
+fn assign(v: &Variable, krate: &TokenStream2) -> TokenStream2 {
+    let field = &v.field;
+    let ty = &v.ty;
+    let bounds = (v.min.is_some() || v.max.is_some()).then(|| {
+        let spec = spec(v, krate);
+        quote!(#spec.check_bounds(value)?;)
+    });
+    let converted = if is_type(ty, "f64") {
+        quote!(value)
+    } else {
+        quote!(value as #ty)
+    };
+    // `as` would truncate fractions and saturate at the type's limits
+    let integral = (v.kind == Kind::Integer).then(|| quote! {
+        if value.fract() != 0.0 || value < <#ty>::MIN as f64 || value >= <#ty>::MAX as f64 + 1.0 {
+            return Err(#krate::ComponentError::InvalidInput(
+                format!("Value for '{}' must be a whole number within {}, got: {}", name, stringify!(#ty), value)
+            ));
+        }
+    });
+
+    quote! {
+        if !value.is_finite() {
+            return Err(#krate::ComponentError::InvalidInput(
+                format!("Value for '{}' must be finite, got: {}", name, value)
+            ));
+        }
+        #integral
+        #bounds
+        self.#field = #converted;
+        Ok(())
+    }
+}
+
+fn computed_error(krate: &TokenStream2) -> TokenStream2 {
+    quote! {
+        Err(#krate::ComponentError::InvalidInput(
+            format!("'{}' is computed by the model and cannot be set", name)
+        ))
+    }
+}
+
+fn type_error(type_name: &str, krate: &TokenStream2) -> TokenStream2 {
+    let message = format!("'{{}}' is of type {}", type_name);
+    quote! {
+        Err(#krate::ComponentError::InvalidInput(format!(#message, name)))
+    }
+}
+
+/// Builds the variable's `IOSpec`
+fn spec(v: &Variable, krate: &TokenStream2) -> TokenStream2 {
+    let name = &v.name;
+    let io_type = match v.kind {
+        Kind::Real => quote!(#krate::IOType::Real),
+        Kind::Boolean => quote!(#krate::IOType::Boolean),
+        Kind::Integer => quote!(#krate::IOType::Integer),
+    };
+    let causality = match v.role {
+        Role::Input => quote!(#krate::Causality::Input),
+        Role::Output => quote!(#krate::Causality::Output),
+        Role::Parameter => quote!(#krate::Causality::Parameter),
+        Role::State => quote!(#krate::Causality::Local),
+    };
+    let variability = match (v.role, v.kind) {
+        (Role::Parameter, _) if v.fixed => quote!(#krate::Variability::Fixed),
+        (Role::Parameter, _) => quote!(#krate::Variability::Tunable),
+        (_, Kind::Real) => quote!(#krate::Variability::Continuous),
+        _ => quote!(#krate::Variability::Discrete),
+    };
+
+    let unit = v.unit.as_ref().map(|unit| quote!(.with_unit(#unit)));
+    let description = v.description.as_ref().map(|d| quote!(.with_description(#d)));
+    let min = v.min.as_ref().map(|min| quote!(.with_min(#min)));
+    let max = v.max.as_ref().map(|max| quote!(.with_max(#max)));
+    let start = v.start.as_ref().map(|start| {
+        match v.kind {
+            Kind::Real => quote!(.with_start(#start)),
+            Kind::Boolean => quote!(.with_start(if #start { 1.0 } else { 0.0 })),
+            Kind::Integer => quote!(.with_start((#start) as f64)),
+        }
+    });
+
+    quote! {
+        #krate::IOSpec::new(#name, #io_type, #causality, #variability)
+            #unit #description #min #max #start
+    }
+}
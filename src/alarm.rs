@@ -0,0 +1,624 @@
+//! Threshold alarms and invariant monitors
+//!
+//! An `Alarm` is a condition over simulation variables, written as text:
+//!
+//! ```text
+//! habitat_1.temperature < 273.15 for 60 s
+//! heaterStatus == 1 and temperature > 310
+//! not (o2 >= 19.5 and o2 <= 23.5) or co2 > 0.5
+//! ```
+//!
+//! Comparisons (`<`, `<=`, `>`, `>=`, `==`, `!=`) combine with `and`, `or`,
+//! `not` and parentheses. A trailing `for <duration>` (`ms`, `s`, `min`,
+//! `h`) debounces the alarm: it is raised only once the condition has held
+//! that long. Hysteresis keeps an active alarm from chattering around its
+//! threshold: while raised, `<` and `<=` comparisons hold up to
+//! `hysteresis` above the threshold, and `>` and `>=` comparisons down to
+//! `hysteresis` below it.
+//!
+//! An `AlarmMonitor` evaluates its alarms against a `RecordSource` and
+//! reports `Raised` / `Cleared` events with timestamps. `attach` evaluates
+//! it after every step of a registry or runtime:
+//!
+//! ```no_run
+//! use modelica_rust_ffi::alarm::{Alarm, AlarmMonitor};
+//! use std::sync::{Arc, Mutex};
+//!
+//! # let mut registry = modelica_rust_ffi::ComponentRegistry::new();
+//! let mut monitor = AlarmMonitor::new();
+//! monitor.add(Alarm::new("habitat_1 freezing", "habitat_1.temperature < 273.15 for 60 s")?
+//!     .with_hysteresis(0.5))?;
+//! monitor.add(Alarm::new("heater fault", "heaterStatus == 1 and temperature > 310")?
+//!     .in_component("habitat_1"))?;
+//!
+//! let monitor = Arc::new(Mutex::new(monitor));
+//! AlarmMonitor::attach(&monitor, &mut registry, |event| {
+//!     println!("t = {} s: {} {:?}", event.time, event.alarm, event.kind);
+//! })?;
+//! # Ok::<(), modelica_rust_ffi::ComponentError>(())
+//! ```
+
+use crate::component::{ComponentError, ComponentResult};
+use crate::observer::{Observable, ObserverId};
+use crate::recorder::RecordSource;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Tolerance for accumulated rounding in debounce times
+const TIME_EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Operator {
+    fn negated(self) -> Self {
+        match self {
+            Operator::Less => Operator::GreaterEqual,
+            Operator::LessEqual => Operator::Greater,
+            Operator::Greater => Operator::LessEqual,
+            Operator::GreaterEqual => Operator::Less,
+            Operator::Equal => Operator::NotEqual,
+            Operator::NotEqual => Operator::Equal,
+        }
+    }
+
+    /// Compares, relaxing ordered comparisons by `slack`
+    fn holds(self, lhs: f64, rhs: f64, slack: f64) -> bool {
+        match self {
+            Operator::Less => lhs < rhs + slack,
+            Operator::LessEqual => lhs <= rhs + slack,
+            Operator::Greater => lhs > rhs - slack,
+            Operator::GreaterEqual => lhs >= rhs - slack,
+            Operator::Equal => lhs == rhs,
+            Operator::NotEqual => lhs != rhs,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Operator::Less => "<",
+            Operator::LessEqual => "<=",
+            Operator::Greater => ">",
+            Operator::GreaterEqual => ">=",
+            Operator::Equal => "==",
+            Operator::NotEqual => "!=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Variable(String),
+    Constant(f64),
+}
+
+/// A parsed condition; `not` is pushed down into the comparisons
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Compare(Operand, Operator, Operand),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+impl Condition {
+    fn negated(self) -> Self {
+        match self {
+            Condition::Compare(lhs, op, rhs) => Condition::Compare(lhs, op.negated(), rhs),
+            Condition::And(a, b) => Condition::Or(Box::new(a.negated()), Box::new(b.negated())),
+            Condition::Or(a, b) => Condition::And(Box::new(a.negated()), Box::new(b.negated())),
+        }
+    }
+
+    fn evaluate<S: RecordSource + ?Sized>(&self, source: &S, prefix: &str, slack: f64) -> ComponentResult<bool> {
+        let value = |operand: &Operand| match operand {
+            Operand::Variable(name) => source.read_variable(&format!("{}{}", prefix, name)),
+            Operand::Constant(value) => Ok(*value),
+        };
+        Ok(match self {
+            Condition::Compare(lhs, op, rhs) => op.holds(value(lhs)?, value(rhs)?, slack),
+            Condition::And(a, b) => a.evaluate(source, prefix, slack)? && b.evaluate(source, prefix, slack)?,
+            Condition::Or(a, b) => a.evaluate(source, prefix, slack)? || b.evaluate(source, prefix, slack)?,
+        })
+    }
+
+    fn variables<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Condition::Compare(lhs, _, rhs) => {
+                for operand in [lhs, rhs] {
+                    if let Operand::Variable(name) = operand {
+                        names.push(name);
+                    }
+                }
+            }
+            Condition::And(a, b) | Condition::Or(a, b) => {
+                a.variables(names);
+                b.variables(names);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Variable(name) => write!(f, "{}", name),
+            Operand::Constant(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// A named condition with debounce and hysteresis
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alarm {
+    name: String,
+    source: String,
+    condition: Condition,
+    prefix: String,
+    raise_delay: f64,
+    clear_delay: f64,
+    hysteresis: f64,
+}
+
+impl Alarm {
+    /// Parses `condition`, including an optional trailing `for <duration>`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::InvalidInput` describing the syntax error.
+    pub fn new(name: &str, condition: &str) -> ComponentResult<Self> {
+        let invalid = |message: String| ComponentError::InvalidInput(
+            format!("Invalid alarm condition '{}': {}", condition, message)
+        );
+
+        let tokens = tokenize(condition).map_err(invalid)?;
+        let mut parser = Parser { tokens, position: 0 };
+        let parsed = parser.or().map_err(invalid)?;
+        let raise_delay = parser.duration().map_err(invalid)?;
+        if let Some(token) = parser.peek() {
+            return Err(invalid(format!("unexpected '{}'", token)));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            source: condition.to_string(),
+            condition: parsed,
+            prefix: String::new(),
+            raise_delay,
+            clear_delay: 0.0,
+            hysteresis: 0.0,
+        })
+    }
+
+    /// Reads every variable as `component.variable`
+    pub fn in_component(mut self, component: &str) -> Self {
+        self.prefix = format!("{}.", component);
+        self
+    }
+
+    /// Relaxes thresholds by `hysteresis` while the alarm is raised
+    pub fn with_hysteresis(mut self, hysteresis: f64) -> Self {
+        self.hysteresis = hysteresis.abs();
+        self
+    }
+
+    /// Seconds the condition must have held before the alarm is raised
+    ///
+    /// Overrides a `for` clause in the condition.
+    pub fn with_raise_delay(mut self, seconds: f64) -> Self {
+        self.raise_delay = seconds.max(0.0);
+        self
+    }
+
+    /// Seconds the condition must have stopped holding before the alarm clears
+    pub fn with_clear_delay(mut self, seconds: f64) -> Self {
+        self.clear_delay = seconds.max(0.0);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The condition as written
+    pub fn condition(&self) -> &str {
+        &self.source
+    }
+
+    /// Fully qualified names of the variables the condition reads
+    pub fn variables(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        self.condition.variables(&mut names);
+        let mut qualified: Vec<String> = Vec::new();
+        for name in names {
+            let name = format!("{}{}", self.prefix, name);
+            if !qualified.contains(&name) {
+                qualified.push(name);
+            }
+        }
+        qualified
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlarmEventKind {
+    Raised,
+    Cleared,
+    /// An attached monitor failed to evaluate the alarm, e.g. because a
+    /// component it reads was removed; the alarm keeps its state
+    Error(String),
+}
+
+/// An alarm changing state
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlarmEvent {
+    pub time: f64,
+    /// Empty for an `Error` concerning the whole monitor
+    pub alarm: String,
+    pub kind: AlarmEventKind,
+    /// When the condition started (raise) or stopped (clear) holding
+    pub since: f64,
+}
+
+struct AlarmState {
+    alarm: Alarm,
+    active: bool,
+    /// Since when the condition has disagreed with `active`
+    pending_since: Option<f64>,
+}
+
+impl AlarmState {
+    /// Evaluates the alarm at `time`; an event if it changes state
+    fn update<S: RecordSource + ?Sized>(&mut self, time: f64, source: &S) -> ComponentResult<Option<AlarmEvent>> {
+        let alarm = &self.alarm;
+        let slack = if self.active { alarm.hysteresis } else { 0.0 };
+        let holds = alarm.condition.evaluate(source, &alarm.prefix, slack)?;
+
+        if holds == self.active {
+            self.pending_since = None;
+            return Ok(None);
+        }
+
+        let since = *self.pending_since.get_or_insert(time);
+        let delay = if self.active { alarm.clear_delay } else { alarm.raise_delay };
+        if time - since + TIME_EPSILON < delay {
+            return Ok(None);
+        }
+
+        self.active = holds;
+        self.pending_since = None;
+        Ok(Some(AlarmEvent {
+            time,
+            alarm: alarm.name.clone(),
+            kind: if holds { AlarmEventKind::Raised } else { AlarmEventKind::Cleared },
+            since,
+        }))
+    }
+}
+
+/// Evaluates alarms and tracks which are raised
+#[derive(Default)]
+pub struct AlarmMonitor {
+    alarms: Vec<AlarmState>,
+}
+
+impl AlarmMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an alarm; names must be unique
+    pub fn add(&mut self, alarm: Alarm) -> ComponentResult<()> {
+        if self.alarms.iter().any(|state| state.alarm.name == alarm.name) {
+            return Err(ComponentError::InvalidInput(format!("Alarm '{}' already exists", alarm.name)));
+        }
+        self.alarms.push(AlarmState { alarm, active: false, pending_since: None });
+        Ok(())
+    }
+
+    /// Removes an alarm; false if there is none by that name
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.alarms.len();
+        self.alarms.retain(|state| state.alarm.name != name);
+        self.alarms.len() < before
+    }
+
+    pub fn alarms(&self) -> impl Iterator<Item = &Alarm> {
+        self.alarms.iter().map(|state| &state.alarm)
+    }
+
+    pub fn is_active(&self, name: &str) -> bool {
+        self.alarms.iter().any(|state| state.alarm.name == name && state.active)
+    }
+
+    /// Names of the raised alarms
+    pub fn active(&self) -> impl Iterator<Item = &str> {
+        self.alarms.iter().filter(|state| state.active).map(|state| state.alarm.name.as_str())
+    }
+
+    /// Checks that every variable the alarms read exists in `source`
+    pub fn validate<S: RecordSource + ?Sized>(&self, source: &S) -> ComponentResult<()> {
+        for state in &self.alarms {
+            for variable in state.alarm.variables() {
+                source.read_variable(&variable)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluates every alarm at `time` and returns the resulting events
+    ///
+    /// Call with increasing times, e.g. after every step; debounce delays
+    /// are measured between these calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::VariableNotFound` if a variable can't be
+    /// read. Alarms evaluated before the failing one keep their new state.
+    pub fn evaluate<S: RecordSource + ?Sized>(&mut self, time: f64, source: &S) -> ComponentResult<Vec<AlarmEvent>> {
+        let mut events = Vec::new();
+        for state in &mut self.alarms {
+            events.extend(state.update(time, source)?);
+        }
+        Ok(events)
+    }
+
+    /// Clears every alarm without reporting events
+    pub fn reset(&mut self) {
+        for state in &mut self.alarms {
+            state.active = false;
+            state.pending_since = None;
+        }
+    }
+
+    /// Evaluates `monitor` after every step of `source`, passing each event to `on_alarm`
+    ///
+    /// An alarm that can't be evaluated, e.g. because its component was
+    /// removed from the registry, is reported as an `AlarmEventKind::Error`
+    /// event on every step while the others are still evaluated. A poisoned
+    /// monitor lock is reported the same way, with an empty alarm name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::VariableNotFound` if an alarm reads a
+    /// variable `source` doesn't have.
+    pub fn attach<S, F>(monitor: &Arc<Mutex<Self>>, source: &mut S, mut on_alarm: F) -> ComponentResult<ObserverId>
+    where
+        S: Observable,
+        F: FnMut(&AlarmEvent) + Send + Sync + 'static,
+    {
+        monitor.lock()
+            .map_err(|_| ComponentError::ThreadSafetyError("Alarm monitor lock poisoned".to_string()))?
+            .validate(source)?;
+
+        let monitor = Arc::clone(monitor);
+        Ok(source.on_after_step(move |source, step| {
+            let error = |alarm: &str, message: String| AlarmEvent {
+                time: step.time,
+                alarm: alarm.to_string(),
+                kind: AlarmEventKind::Error(message),
+                since: step.time,
+            };
+            let Ok(mut monitor) = monitor.lock() else {
+                on_alarm(&error("", "Alarm monitor lock poisoned".to_string()));
+                return;
+            };
+            for state in &mut monitor.alarms {
+                match state.update(step.time, source) {
+                    Ok(Some(event)) => on_alarm(&event),
+                    Ok(None) => {}
+                    Err(e) => on_alarm(&error(&state.alarm.name, e.to_string())),
+                }
+            }
+        }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Number(f64),
+    Operator(Operator),
+    And,
+    Or,
+    Not,
+    For,
+    Minus,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Identifier(name) => write!(f, "{}", name),
+            Token::Number(value) => write!(f, "{}", value),
+            Token::Operator(op) => write!(f, "{}", op.symbol()),
+            Token::And => write!(f, "and"),
+            Token::Or => write!(f, "or"),
+            Token::Not => write!(f, "not"),
+            Token::For => write!(f, "for"),
+            Token::Minus => write!(f, "-"),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let (token, width) = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            ('-', _) => (Token::Minus, 1),
+            ('<', Some('=')) => (Token::Operator(Operator::LessEqual), 2),
+            ('<', _) => (Token::Operator(Operator::Less), 1),
+            ('>', Some('=')) => (Token::Operator(Operator::GreaterEqual), 2),
+            ('>', _) => (Token::Operator(Operator::Greater), 1),
+            ('=', Some('=')) => (Token::Operator(Operator::Equal), 2),
+            ('!', Some('=')) => (Token::Operator(Operator::NotEqual), 2),
+            ('!', _) => (Token::Not, 1),
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            (c, _) if c.is_ascii_digit() || c == '.' => {
+                let mut end = i;
+                while end < chars.len() {
+                    let d = chars[end];
+                    let exponent_sign = (d == '+' || d == '-') && matches!(chars[end - 1], 'e' | 'E');
+                    if d.is_ascii_digit() || d == '.' || d == 'e' || d == 'E' || exponent_sign {
+                        end += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let literal: String = chars[i..end].iter().collect();
+                let value = literal.parse().map_err(|_| format!("invalid number '{}' at {}", literal, i))?;
+                (Token::Number(value), end - i)
+            }
+            (c, _) if c.is_alphabetic() || c == '_' => {
+                let mut end = i;
+                while end < chars.len() && (chars[end].is_alphanumeric() || "_.[]".contains(chars[end])) {
+                    end += 1;
+                }
+                let word: String = chars[i..end].iter().collect();
+                let token = match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "for" => Token::For,
+                    "true" => Token::Number(1.0),
+                    "false" => Token::Number(0.0),
+                    _ => Token::Identifier(word),
+                };
+                (token, end - i)
+            }
+            (c, _) => return Err(format!("unexpected '{}' at {}", c, i)),
+        };
+        tokens.push(token);
+        i += width;
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over `or := and ('or' and)*`, `and := not ('and' not)*`,
+/// `not := 'not' not | '(' or ')' | operand op operand`
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Condition, String> {
+        let mut condition = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, String> {
+        let mut condition = self.not()?;
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            condition = Condition::And(Box::new(condition), Box::new(self.not()?));
+        }
+        Ok(condition)
+    }
+
+    fn not(&mut self) -> Result<Condition, String> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.position += 1;
+                Ok(self.not()?.negated())
+            }
+            Some(Token::Open) => {
+                self.position += 1;
+                let condition = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(condition),
+                    other => Err(format!("expected ')', found {}", describe(other))),
+                }
+            }
+            _ => {
+                let lhs = self.operand()?;
+                let op = match self.next() {
+                    Some(Token::Operator(op)) => op,
+                    other => return Err(format!("expected a comparison after '{}', found {}", lhs, describe(other))),
+                };
+                let rhs = self.operand()?;
+                Ok(Condition::Compare(lhs, op, rhs))
+            }
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, String> {
+        match self.next() {
+            Some(Token::Identifier(name)) => Ok(Operand::Variable(name)),
+            Some(Token::Number(value)) => Ok(Operand::Constant(value)),
+            Some(Token::Minus) => match self.next() {
+                Some(Token::Number(value)) => Ok(Operand::Constant(-value)),
+                other => Err(format!("expected a number after '-', found {}", describe(other))),
+            },
+            other => Err(format!("expected a variable or number, found {}", describe(other))),
+        }
+    }
+
+    /// `for <number> [ms|s|min|h]`, in seconds; 0 without a `for` clause
+    fn duration(&mut self) -> Result<f64, String> {
+        if self.peek() != Some(&Token::For) {
+            return Ok(0.0);
+        }
+        self.position += 1;
+
+        let value = match self.next() {
+            Some(Token::Number(value)) => value,
+            other => return Err(format!("expected a duration after 'for', found {}", describe(other))),
+        };
+        let scale = match self.peek() {
+            Some(Token::Identifier(unit)) => {
+                let scale = match unit.as_str() {
+                    "ms" => 1e-3,
+                    "s" => 1.0,
+                    "min" => 60.0,
+                    "h" => 3600.0,
+                    other => return Err(format!("unknown duration unit '{}'", other)),
+                };
+                self.position += 1;
+                scale
+            }
+            _ => 1.0,
+        };
+        Ok(value * scale)
+    }
+}
+
+fn describe(token: Option<Token>) -> String {
+    token.map_or("end of input".to_string(), |token| format!("'{}'", token))
+}
@@ -0,0 +1,266 @@
+//! `modelica-sim`: list, inspect and run models from the command line
+//!
+//! ```text
+//! modelica-sim list
+//! modelica-sim describe SimpleThermalMVP
+//! modelica-sim run scenario.toml -o results.csv --set habitat_1.heaterPower=900
+//! modelica-sim compare SimpleThermalMVP_res.mat results.csv --rel-tol 1e-3
+//! ```
+
+use clap::{Parser, Subcommand};
+use modelica_rust_ffi::compare::{self, ResultSet, Tolerance};
+use modelica_rust_ffi::{
+    ComponentError, ComponentFactory, CsvSink, IOSpec, MatSink, Recorder, ResultSink, Scenario, ScenarioValue,
+};
+use std::error::Error;
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "modelica-sim", version, about = "Run Modelica simulation components")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the available component types
+    List,
+
+    /// Print a component type's variables
+    Describe {
+        /// Component type, e.g. SimpleThermalMVP
+        component_type: String,
+
+        /// Print the metadata as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Simulate a TOML or JSON scenario and write the results as CSV or MAT
+    Run {
+        /// Scenario file
+        scenario: PathBuf,
+
+        /// Write results here instead of stdout; a .mat extension writes an
+        /// OpenModelica result file, .parquet (with the arrow feature) Parquet
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Variables to record as glob patterns, e.g. 'habitat_*.temperature'
+        /// (repeatable; default: every output)
+        #[arg(long, value_name = "PATTERN")]
+        select: Vec<String>,
+
+        /// Seconds between recorded rows (default: every step)
+        #[arg(long)]
+        interval: Option<f64>,
+
+        /// Override a parameter, e.g. habitat_1.heaterPower=900 (repeatable)
+        #[arg(long = "set", visible_alias = "override", value_name = "COMPONENT.PARAMETER=VALUE")]
+        overrides: Vec<String>,
+
+        /// Override the scenario's stop time (s)
+        #[arg(long)]
+        stop_time: Option<f64>,
+
+        /// Override the scenario's step size (s)
+        #[arg(long)]
+        step_size: Option<f64>,
+    },
+
+    /// Compare results (CSV or MAT) against a reference; fails on differences
+    Compare {
+        /// Reference results, e.g. an OpenModelica _res.mat
+        reference: PathBuf,
+
+        /// Results to check
+        actual: PathBuf,
+
+        /// Variables to compare as glob patterns (repeatable; default: all)
+        #[arg(long, value_name = "PATTERN")]
+        select: Vec<String>,
+
+        /// Absolute tolerance
+        #[arg(long, default_value_t = Tolerance::default().absolute)]
+        abs_tol: f64,
+
+        /// Relative tolerance
+        #[arg(long, default_value_t = Tolerance::default().relative)]
+        rel_tol: f64,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::List => list(),
+        Command::Describe { component_type, json } => describe(&component_type, json),
+        Command::Run { scenario, output, select, interval, overrides, stop_time, step_size } => {
+            run(&scenario, output, &select, interval, &overrides, stop_time, step_size)
+        }
+        Command::Compare { reference, actual, select, abs_tol, rel_tol } => {
+            compare(&reference, &actual, &select, Tolerance::new(abs_tol, rel_tol))
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn list() -> Result<(), Box<dyn Error>> {
+    let factory = ComponentFactory::with_builtins();
+    for type_name in factory.list_types() {
+        let category = factory.metadata(&type_name)
+            .map(|m| m.component_type.as_str())
+            .unwrap_or("");
+        println!("{:<24} {}", type_name, category);
+    }
+    Ok(())
+}
+
+fn describe(type_name: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let factory = ComponentFactory::with_builtins();
+    let metadata = factory.metadata(type_name)
+        .ok_or_else(|| ComponentError::UnknownComponentType(type_name.to_string()))?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(metadata)?);
+        return Ok(());
+    }
+
+    println!("{} ({})", metadata.name, metadata.component_type);
+    print_variables("Parameters", &metadata.parameters);
+    print_variables("Inputs", &metadata.inputs);
+    print_variables("Outputs", &metadata.outputs);
+    print_variables("Locals", &metadata.locals);
+    Ok(())
+}
+
+fn print_variables(title: &str, variables: &[IOSpec]) {
+    if variables.is_empty() {
+        return;
+    }
+
+    println!("\n{}:", title);
+    for spec in variables {
+        let range = match (spec.min, spec.max) {
+            (None, None) => String::new(),
+            (min, max) => format!(
+                "[{}, {}]",
+                min.map_or("-inf".to_string(), |v| v.to_string()),
+                max.map_or("inf".to_string(), |v| v.to_string()),
+            ),
+        };
+        println!(
+            "  {:<18} {:<8} {:<11} {:<6} {:>8} {:<14} {}",
+            spec.name,
+            format!("{:?}", spec.io_type),
+            format!("{:?}", spec.variability).to_lowercase(),
+            spec.unit.as_deref().unwrap_or(""),
+            spec.start.map_or(String::new(), |v| v.to_string()),
+            range,
+            spec.description.as_deref().unwrap_or(""),
+        );
+    }
+}
+
+fn run(
+    path: &PathBuf,
+    output: Option<PathBuf>,
+    select: &[String],
+    interval: Option<f64>,
+    overrides: &[String],
+    stop_time: Option<f64>,
+    step_size: Option<f64>,
+) -> Result<(), Box<dyn Error>> {
+    let mut scenario = Scenario::from_file(path)?;
+    if let Some(stop_time) = stop_time {
+        scenario.stop_time = stop_time;
+    }
+    if let Some(step_size) = step_size {
+        scenario.step_size = step_size;
+    }
+    for assignment in overrides {
+        apply_override(&mut scenario, assignment)?;
+    }
+    scenario.validate()?;
+
+    let sink: Box<dyn ResultSink> = match output {
+        Some(path) if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("mat")) => {
+            Box::new(MatSink::create(path)?)
+        }
+        #[cfg(feature = "arrow")]
+        Some(path) if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("parquet")) => {
+            Box::new(modelica_rust_ffi::ParquetSink::create(path)?)
+        }
+        Some(path) => Box::new(CsvSink::create(path)?),
+        None => Box::new(CsvSink::new(BufWriter::new(io::stdout().lock()))),
+    };
+
+    let mut recorder = Recorder::new(sink);
+    for pattern in select {
+        recorder = recorder.select(pattern);
+    }
+    if let Some(interval) = interval {
+        recorder = recorder.with_interval(interval);
+    }
+
+    let mut registry = scenario.build_registry()?;
+    recorder.start(&registry)?;
+    scenario.run_with(&mut registry, |time, registry| recorder.sample(time, registry))?;
+    recorder.finish()?;
+    Ok(())
+}
+
+fn compare(reference: &PathBuf, actual: &PathBuf, select: &[String], tolerance: Tolerance) -> Result<(), Box<dyn Error>> {
+    let reference = ResultSet::load(reference)?;
+    let actual = ResultSet::load(actual)?;
+
+    let comparison = if select.is_empty() {
+        compare::compare(&reference, &actual, tolerance)?
+    } else {
+        let patterns: Vec<&str> = select.iter().map(String::as_str).collect();
+        compare::compare_matching(&reference, &actual, &patterns, tolerance)?
+    };
+    println!("{}", comparison);
+
+    if comparison.passed() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} variables out of tolerance, {} missing",
+            comparison.failures().count(),
+            comparison.missing.len()
+        ).into())
+    }
+}
+
+/// Applies `component.parameter=value` to the scenario
+fn apply_override(scenario: &mut Scenario, assignment: &str) -> Result<(), Box<dyn Error>> {
+    let (target, value) = assignment.split_once('=')
+        .ok_or_else(|| format!("Expected COMPONENT.PARAMETER=VALUE, got '{}'", assignment))?;
+    let (component, parameter) = target.split_once('.')
+        .ok_or_else(|| format!("Expected COMPONENT.PARAMETER=VALUE, got '{}'", assignment))?;
+
+    let value = match value.trim() {
+        "true" => ScenarioValue::Bool(true),
+        "false" => ScenarioValue::Bool(false),
+        number => ScenarioValue::Real(number.parse()
+            .map_err(|_| format!("Invalid value '{}' for {}", number, target))?),
+    };
+
+    let spec = scenario.components.iter_mut()
+        .find(|c| c.name == component)
+        .ok_or_else(|| format!("Scenario has no component '{}'", component))?;
+    spec.parameters.insert(parameter.to_string(), value);
+    Ok(())
+}
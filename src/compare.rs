@@ -0,0 +1,590 @@
+//! Comparing simulation results
+//!
+//! Loads result sets from CSV (as written by `CsvSink` or OpenModelica's
+//! `-outputFormat=csv`) or MAT v4 files (OpenModelica's default result
+//! format, or `MatSink`), aligns two of them on time and reports per
+//! variable the largest absolute and relative error and the first time the
+//! tolerance is violated, in the spirit of OpenModelica's
+//! `diffSimulationResults`.
+//!
+//! ```no_run
+//! use modelica_rust_ffi::compare::{compare, ResultSet, Tolerance};
+//!
+//! let reference = ResultSet::load("SimpleThermalMVP_res.mat")?;
+//! let actual = ResultSet::load("results.csv")?;
+//!
+//! let comparison = compare(&reference, &actual, Tolerance::new(1e-4, 1e-3))?;
+//! if !comparison.passed() {
+//!     println!("{}", comparison);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::component::{ComponentError, ComponentResult};
+use crate::recorder::glob_match;
+use std::fmt;
+use std::path::Path;
+
+/// Time series of named variables sharing one time axis
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultSet {
+    time: Vec<f64>,
+    names: Vec<String>,
+    values: Vec<Vec<f64>>,
+}
+
+impl ResultSet {
+    /// Creates an empty result set on the given time axis
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::InvalidResults` if `time` decreases or is
+    /// not finite. Repeated time points (events) are allowed.
+    pub fn new(time: Vec<f64>) -> ComponentResult<Self> {
+        if let Some(bad) = time.iter().find(|t| !t.is_finite()) {
+            return Err(ComponentError::InvalidResults(format!("Non-finite time {}", bad)));
+        }
+        if let Some(pair) = time.windows(2).find(|pair| pair[1] < pair[0]) {
+            return Err(ComponentError::InvalidResults(
+                format!("Time decreases from {} to {}", pair[0], pair[1])
+            ));
+        }
+        Ok(Self { time, names: Vec::new(), values: Vec::new() })
+    }
+
+    /// Adds (or replaces) a variable with one value per time point
+    pub fn insert(&mut self, name: &str, values: Vec<f64>) -> ComponentResult<()> {
+        if values.len() != self.time.len() {
+            return Err(ComponentError::InvalidResults(format!(
+                "Variable '{}' has {} values for {} time points", name, values.len(), self.time.len()
+            )));
+        }
+        match self.names.iter().position(|n| n == name) {
+            Some(index) => self.values[index] = values,
+            None => {
+                self.names.push(name.to_string());
+                self.values.push(values);
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads a `.mat` file as MAT v4, anything else as CSV
+    pub fn load<P: AsRef<Path>>(path: P) -> ComponentResult<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| {
+            ComponentError::InvalidResults(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+
+        let is_mat = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("mat"));
+        let result = if is_mat {
+            Self::from_mat_bytes(&bytes)
+        } else {
+            let text = String::from_utf8(bytes)
+                .map_err(|_| ComponentError::InvalidResults("CSV is not valid UTF-8".to_string()))?;
+            Self::from_csv_str(&text)
+        };
+        result.map_err(|e| match e {
+            ComponentError::InvalidResults(message) => {
+                ComponentError::InvalidResults(format!("{}: {}", path.display(), message))
+            }
+            other => other,
+        })
+    }
+
+    /// Parses CSV with a header row
+    ///
+    /// Header cells may carry a unit as `name [unit]`, which is dropped. The
+    /// column named `time` is the time axis, or the first column if there
+    /// is none.
+    pub fn from_csv_str(text: &str) -> ComponentResult<Self> {
+        let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+        let (_, header) = lines.next()
+            .ok_or_else(|| ComponentError::InvalidResults("CSV has no header".to_string()))?;
+        let names: Vec<String> = split_csv(header).iter().map(|cell| strip_unit(cell)).collect();
+        let time_column = names.iter().position(|n| n == "time").unwrap_or(0);
+
+        let mut columns = vec![Vec::new(); names.len()];
+        for (number, line) in lines {
+            let cells = split_csv(line);
+            if cells.len() != names.len() {
+                return Err(ComponentError::InvalidResults(format!(
+                    "Line {} has {} values, expected {}", number + 1, cells.len(), names.len()
+                )));
+            }
+            for (column, cell) in columns.iter_mut().zip(&cells) {
+                let value = cell.trim().parse().map_err(|_| ComponentError::InvalidResults(
+                    format!("Line {}: invalid number '{}'", number + 1, cell)
+                ))?;
+                column.push(value);
+            }
+        }
+
+        let mut result = Self::new(columns[time_column].clone())?;
+        for (index, (name, values)) in names.iter().zip(columns).enumerate() {
+            if index != time_column {
+                result.insert(name, values)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Parses an OpenModelica MAT v4 result file (`binTrans` or `binNormal`)
+    ///
+    /// Parameters stored in `data_1` become constant series on the `data_2`
+    /// time axis; negated aliases are resolved.
+    pub fn from_mat_bytes(bytes: &[u8]) -> ComponentResult<Self> {
+        let matrices = read_matrices(bytes)?;
+        let find = |name: &str| matrices.iter().find(|m| m.name == name)
+            .ok_or_else(|| ComponentError::InvalidResults(format!("Missing matrix '{}'", name)));
+
+        let aclass = find("Aclass")?;
+        let transposed = match aclass.row(3).as_str() {
+            "binTrans" => true,
+            "binNormal" => false,
+            other => return Err(ComponentError::InvalidResults(
+                format!("Unsupported result layout '{}'", other)
+            )),
+        };
+
+        let names = find("name")?.strings(transposed);
+        let info = find("dataInfo")?;
+        if info.data.len() < names.len() * 4 {
+            return Err(ComponentError::InvalidResults("dataInfo is shorter than name".to_string()));
+        }
+        let data_2 = find("data_2")?;
+        let data_1 = find("data_1").ok();
+
+        // (variables, samples) of a data matrix in either layout
+        let shape = |m: &Matrix| if transposed { (m.rows, m.cols) } else { (m.cols, m.rows) };
+        let element = |m: &Matrix, variable: usize, sample: usize| {
+            if transposed { m.data[sample * m.rows + variable] } else { m.data[variable * m.rows + sample] }
+        };
+
+        let (variables_2, samples) = shape(data_2);
+        if variables_2 == 0 {
+            return Err(ComponentError::InvalidResults("data_2 has no time row".to_string()));
+        }
+        let mut result = Self::new((0..samples).map(|s| element(data_2, 0, s)).collect())?;
+
+        for (index, name) in names.iter().enumerate() {
+            let field = |row: usize| -> f64 {
+                if transposed { info.data[index * info.rows + row] } else { info.data[row * info.rows + index] }
+            };
+            let (matrix, column) = (field(0) as i32, field(1) as i32);
+            let (variable, sign) = ((column.unsigned_abs() as usize).saturating_sub(1), column.signum() as f64);
+
+            let values = match matrix {
+                // The abscissa, i.e. time
+                0 => continue,
+                1 => {
+                    let data_1 = data_1.ok_or_else(|| {
+                        ComponentError::InvalidResults(format!("'{}' refers to a missing data_1", name))
+                    })?;
+                    if variable >= shape(data_1).0 || shape(data_1).1 == 0 {
+                        return Err(ComponentError::InvalidResults(format!("'{}' is outside data_1", name)));
+                    }
+                    vec![sign * element(data_1, variable, 0); samples]
+                }
+                2 => {
+                    if variable >= variables_2 {
+                        return Err(ComponentError::InvalidResults(format!("'{}' is outside data_2", name)));
+                    }
+                    (0..samples).map(|s| sign * element(data_2, variable, s)).collect()
+                }
+                other => return Err(ComponentError::InvalidResults(
+                    format!("'{}' refers to unknown data matrix {}", name, other)
+                )),
+            };
+            result.insert(name, values)?;
+        }
+        Ok(result)
+    }
+
+    pub fn time(&self) -> &[f64] {
+        &self.time
+    }
+
+    /// Variable names, excluding time, in file order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    pub fn values(&self, name: &str) -> Option<&[f64]> {
+        self.names.iter().position(|n| n == name).map(|index| self.values[index].as_slice())
+    }
+
+    /// Value of `name` at `time`, linearly interpolated
+    ///
+    /// At a repeated time point (an event) the last value, i.e. the one
+    /// after the event, is returned. `None` if the variable is unknown or
+    /// `time` lies outside the recorded range.
+    pub fn value_at(&self, name: &str, time: f64) -> Option<f64> {
+        let values = self.values(name)?;
+        let after = self.time.partition_point(|&t| t <= time);
+        if after == 0 {
+            return None;
+        }
+
+        let (t0, v0) = (self.time[after - 1], values[after - 1]);
+        if t0 == time {
+            return Some(v0);
+        }
+        let (t1, v1) = (*self.time.get(after)?, values[after]);
+        Some(v0 + (v1 - v0) * (time - t0) / (t1 - t0))
+    }
+
+    /// Number of time points
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+}
+
+/// Allowed deviation of an actual value from the reference
+///
+/// A value passes if `|actual - reference| <= absolute + relative * |reference|`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Tolerance {
+    pub fn new(absolute: f64, relative: f64) -> Self {
+        Self { absolute, relative }
+    }
+
+    pub fn allows(&self, reference: f64, actual: f64) -> bool {
+        (actual - reference).abs() <= self.absolute + self.relative * reference.abs()
+    }
+}
+
+impl Default for Tolerance {
+    /// `1e-4` absolute plus `1e-3` relative
+    fn default() -> Self {
+        Self::new(1e-4, 1e-3)
+    }
+}
+
+/// How one variable differs between two result sets
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableDiff {
+    pub name: String,
+    pub max_abs_error: f64,
+    /// Largest `|actual - reference| / |reference|` over nonzero references
+    pub max_rel_error: f64,
+    /// Where the absolute error is largest
+    pub time_of_max_error: f64,
+    /// First time the tolerance is exceeded, if ever
+    pub first_violation: Option<f64>,
+}
+
+impl VariableDiff {
+    pub fn passed(&self) -> bool {
+        self.first_violation.is_none()
+    }
+}
+
+/// Relative slack when matching the start and stop times of two result sets
+const TIME_EPSILON: f64 = 1e-9;
+
+/// Result of comparing two result sets
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub variables: Vec<VariableDiff>,
+    /// Compared variables absent from the actual results
+    pub missing: Vec<String>,
+    /// The time range both result sets cover, where values were compared
+    pub start_time: f64,
+    pub stop_time: f64,
+    /// First and last time point of the reference
+    pub reference_range: (f64, f64),
+    /// First and last time point of the actual results
+    pub actual_range: (f64, f64),
+}
+
+impl Comparison {
+    /// True if no variable is missing or out of tolerance and both result
+    /// sets cover the same time range
+    pub fn passed(&self) -> bool {
+        self.time_range_matches()
+            && self.missing.is_empty()
+            && self.variables.iter().all(VariableDiff::passed)
+    }
+
+    /// False if the actual results start or stop at a different time than
+    /// the reference, e.g. a run that was cut short
+    pub fn time_range_matches(&self) -> bool {
+        let ((r0, r1), (a0, a1)) = (self.reference_range, self.actual_range);
+        let slack = TIME_EPSILON * (r1 - r0).abs().max(1.0);
+        (a0 - r0).abs() <= slack && (a1 - r1).abs() <= slack
+    }
+
+    /// Variables out of tolerance
+    pub fn failures(&self) -> impl Iterator<Item = &VariableDiff> {
+        self.variables.iter().filter(|diff| !diff.passed())
+    }
+
+    pub fn variable(&self, name: &str) -> Option<&VariableDiff> {
+        self.variables.iter().find(|diff| diff.name == name)
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Compared {} variables over [{}, {}] s", self.variables.len(), self.start_time, self.stop_time)?;
+        writeln!(f, "{:<32} {:>13} {:>13}  first violation", "variable", "max abs error", "max rel error")?;
+        for diff in &self.variables {
+            writeln!(
+                f,
+                "{:<32} {:>13.6e} {:>13.6e}  {}",
+                diff.name,
+                diff.max_abs_error,
+                diff.max_rel_error,
+                diff.first_violation.map_or("-".to_string(), |t| format!("t = {} s", t)),
+            )?;
+        }
+        for name in &self.missing {
+            writeln!(f, "{:<32} missing", name)?;
+        }
+        if !self.time_range_matches() {
+            writeln!(
+                f,
+                "Time range [{}, {}] s differs from the reference [{}, {}] s",
+                self.actual_range.0, self.actual_range.1, self.reference_range.0, self.reference_range.1,
+            )?;
+        }
+        write!(f, "{}", if self.passed() { "PASSED" } else { "FAILED" })
+    }
+}
+
+/// Compares every variable of `reference` against `actual`
+///
+/// Both sets are evaluated at the union of their time points within the
+/// range they share, interpolating linearly. The comparison fails if the
+/// ranges differ, see `Comparison::time_range_matches`.
+///
+/// # Errors
+///
+/// Returns `ComponentError::InvalidResults` if the time ranges do not
+/// overlap.
+pub fn compare(reference: &ResultSet, actual: &ResultSet, tolerance: Tolerance) -> ComponentResult<Comparison> {
+    let names: Vec<&str> = reference.names().collect();
+    compare_names(reference, actual, &names, tolerance)
+}
+
+/// Compares the reference variables matching any of the glob `patterns`
+///
+/// # Errors
+///
+/// Returns `ComponentError::VariableNotFound` for a pattern matching no
+/// reference variable, and `ComponentError::InvalidResults` if the time
+/// ranges do not overlap.
+pub fn compare_matching(
+    reference: &ResultSet,
+    actual: &ResultSet,
+    patterns: &[&str],
+    tolerance: Tolerance,
+) -> ComponentResult<Comparison> {
+    let mut names: Vec<&str> = Vec::new();
+    for pattern in patterns {
+        let matches: Vec<&str> = reference.names().filter(|name| glob_match(pattern, name)).collect();
+        if matches.is_empty() {
+            return Err(ComponentError::VariableNotFound(pattern.to_string()));
+        }
+        for name in matches {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    compare_names(reference, actual, &names, tolerance)
+}
+
+fn compare_names(
+    reference: &ResultSet,
+    actual: &ResultSet,
+    names: &[&str],
+    tolerance: Tolerance,
+) -> ComponentResult<Comparison> {
+    let bounds = |set: &ResultSet| set.time().first().copied().zip(set.time().last().copied());
+    let ((r0, r1), (a0, a1)) = bounds(reference).zip(bounds(actual))
+        .ok_or_else(|| ComponentError::InvalidResults("Result set has no time points".to_string()))?;
+    let (start_time, stop_time) = (r0.max(a0), r1.min(a1));
+    if start_time > stop_time {
+        return Err(ComponentError::InvalidResults(format!(
+            "Time ranges do not overlap: [{}, {}] and [{}, {}]", r0, r1, a0, a1
+        )));
+    }
+
+    let mut times: Vec<f64> = reference.time().iter().chain(actual.time())
+        .copied()
+        .filter(|t| (start_time..=stop_time).contains(t))
+        .collect();
+    times.sort_by(f64::total_cmp);
+    times.dedup();
+
+    let mut comparison = Comparison {
+        variables: Vec::new(),
+        missing: Vec::new(),
+        start_time,
+        stop_time,
+        reference_range: (r0, r1),
+        actual_range: (a0, a1),
+    };
+    for &name in names {
+        if actual.values(name).is_none() {
+            comparison.missing.push(name.to_string());
+            continue;
+        }
+
+        let mut diff = VariableDiff {
+            name: name.to_string(),
+            max_abs_error: 0.0,
+            max_rel_error: 0.0,
+            time_of_max_error: start_time,
+            first_violation: None,
+        };
+        for &time in &times {
+            // Both are defined on the shared range; unknown names were skipped
+            let (Some(expected), Some(value)) = (reference.value_at(name, time), actual.value_at(name, time)) else {
+                continue;
+            };
+
+            let error = (value - expected).abs();
+            if error > diff.max_abs_error || error.is_nan() {
+                diff.max_abs_error = error;
+                diff.time_of_max_error = time;
+            }
+            if expected != 0.0 {
+                diff.max_rel_error = diff.max_rel_error.max(error / expected.abs());
+            }
+            if diff.first_violation.is_none() && !tolerance.allows(expected, value) {
+                diff.first_violation = Some(time);
+            }
+        }
+        comparison.variables.push(diff);
+    }
+    Ok(comparison)
+}
+
+/// Splits a CSV line, honouring double quotes
+fn split_csv(line: &str) -> Vec<String> {
+    let mut cells = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cells.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(String::new()),
+            c => cells.last_mut().unwrap().push(c),
+        }
+    }
+    cells
+}
+
+/// `"temperature [K]"` -> `"temperature"`
+fn strip_unit(cell: &str) -> String {
+    let cell = cell.trim();
+    match cell.rfind(" [") {
+        Some(at) if cell.ends_with(']') => cell[..at].to_string(),
+        _ => cell.to_string(),
+    }
+}
+
+/// Internal: One MAT v4 matrix, converted to `f64`
+struct Matrix {
+    name: String,
+    rows: usize,
+    cols: usize,
+    /// Column-major
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    /// A row of a text matrix, without trailing padding
+    fn row(&self, row: usize) -> String {
+        let text: String = (0..self.cols)
+            .filter_map(|col| self.data.get(col * self.rows + row))
+            .map(|&c| c as u8 as char)
+            .collect();
+        text.trim_end_matches(['\0', ' ']).to_string()
+    }
+
+    /// Strings of a text matrix, one per column if `transposed`, else per row
+    fn strings(&self, transposed: bool) -> Vec<String> {
+        if !transposed {
+            return (0..self.rows).map(|row| self.row(row)).collect();
+        }
+        self.data.chunks(self.rows.max(1))
+            .take(self.cols)
+            .map(|column| {
+                let text: String = column.iter().map(|&c| c as u8 as char).collect();
+                text.trim_end_matches(['\0', ' ']).to_string()
+            })
+            .collect()
+    }
+}
+
+fn read_matrices(bytes: &[u8]) -> ComponentResult<Vec<Matrix>> {
+    let truncated = || ComponentError::InvalidResults("MAT file is truncated".to_string());
+    let int = |at: usize| -> ComponentResult<i32> {
+        bytes.get(at..at + 4)
+            .map(|b| i32::from_le_bytes(b.try_into().expect("4 bytes")))
+            .ok_or_else(truncated)
+    };
+
+    let mut matrices = Vec::new();
+    let mut at = 0;
+    while at < bytes.len() {
+        let type_code = int(at)?;
+        let (rows, cols, imaginary, name_len) = (int(at + 4)?, int(at + 8)?, int(at + 12)?, int(at + 16)?);
+        if !(0..1000).contains(&type_code) {
+            return Err(ComponentError::InvalidResults(format!(
+                "Unsupported MAT v4 type {} (only little-endian files are read)", type_code
+            )));
+        }
+        if rows < 0 || cols < 0 || name_len < 1 || imaginary != 0 {
+            return Err(ComponentError::InvalidResults("Invalid MAT v4 matrix header".to_string()));
+        }
+
+        let name_start = at + 20;
+        let name_end = name_start.checked_add(name_len as usize).ok_or_else(truncated)?;
+        let name_bytes = bytes.get(name_start..name_end).ok_or_else(truncated)?;
+        let name = String::from_utf8_lossy(name_bytes).trim_end_matches('\0').to_string();
+        at = name_end;
+
+        // Precision digit: f64, f32, i32, i16, u16, u8
+        let precision = (type_code / 10) % 10;
+        let width = *[8, 4, 4, 2, 2, 1].get(precision as usize).ok_or_else(|| {
+            ComponentError::InvalidResults(format!("Unsupported MAT v4 type {}", type_code))
+        })?;
+        let too_large = || ComponentError::InvalidResults(format!("MAT v4 matrix '{}' is too large", name));
+        let count = (rows as usize).checked_mul(cols as usize).ok_or_else(too_large)?;
+        let end = count.checked_mul(width).and_then(|size| at.checked_add(size)).ok_or_else(too_large)?;
+        let raw = bytes.get(at..end).ok_or_else(truncated)?;
+        let data = raw.chunks_exact(width)
+            .map(|b| match precision {
+                0 => f64::from_le_bytes(b.try_into().expect("8 bytes")),
+                1 => f32::from_le_bytes(b.try_into().expect("4 bytes")) as f64,
+                2 => i32::from_le_bytes(b.try_into().expect("4 bytes")) as f64,
+                3 => i16::from_le_bytes(b.try_into().expect("2 bytes")) as f64,
+                4 => u16::from_le_bytes(b.try_into().expect("2 bytes")) as f64,
+                _ => b[0] as f64,
+            })
+            .collect();
+        at = end;
+
+        matrices.push(Matrix { name, rows: rows as usize, cols: cols as usize, data });
+    }
+    Ok(matrices)
+}
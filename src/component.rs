@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
 
@@ -29,6 +30,21 @@ pub enum ComponentError {
     
     #[error("Thread safety violation: {0}")]
     ThreadSafetyError(String),
+    
+    #[error("Parameter '{0}' is fixed and can only be changed before initialization")]
+    FixedParameter(String),
+    
+    #[error("Unknown component type '{0}'")]
+    UnknownComponentType(String),
+    
+    #[error("Invalid scenario: {0}")]
+    InvalidScenario(String),
+    
+    #[error("Recording failed: {0}")]
+    RecordingFailed(String),
+    
+    #[error("Invalid result file: {0}")]
+    InvalidResults(String),
 }
 
 pub type ComponentResult<T> = Result<T, ComponentError>;
@@ -56,6 +72,14 @@ pub trait SimulationComponent: Send + Sync {
     /// Reset component to initial state
     fn reset(&mut self) -> ComponentResult<()>;
     
+    /// Change a parameter value
+    ///
+    /// Fixed parameters may only be changed before initialization or after
+    /// a reset; tunable parameters may be changed at any time.
+    fn set_parameter(&mut self, name: &str, _value: f64) -> ComponentResult<()> {
+        Err(ComponentError::VariableNotFound(name.to_string())) // Default implementation
+    }
+    
     /// Get all outputs as a map
     fn get_all_outputs(&self) -> HashMap<String, f64> {
         HashMap::new() // Default implementation
@@ -63,27 +87,191 @@ pub trait SimulationComponent: Send + Sync {
     
     /// Get component metadata
     fn metadata(&self) -> ComponentMetadata;
+
+    /// Total solver calls so far, if the component counts them
+    ///
+    /// Used by `ComponentRegistry::stats`.
+    fn solver_calls(&self) -> Option<u64> {
+        None // Default implementation
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentMetadata {
     pub name: String,
     pub component_type: String,
     pub inputs: Vec<IOSpec>,
     pub outputs: Vec<IOSpec>,
+    pub parameters: Vec<IOSpec>,
+    /// States and other internal variables
+    pub locals: Vec<IOSpec>,
+}
+
+impl ComponentMetadata {
+    /// Build metadata by sorting a variable table by causality
+    ///
+    /// The independent variable (time) is not listed.
+    pub fn from_variables(name: &str, component_type: &str, variables: &[IOSpec]) -> Self {
+        let with_causality = |causality: Causality| -> Vec<IOSpec> {
+            variables.iter()
+                .filter(|v| v.causality == causality)
+                .cloned()
+                .collect()
+        };
+        
+        Self {
+            name: name.to_string(),
+            component_type: component_type.to_string(),
+            inputs: with_causality(Causality::Input),
+            outputs: with_causality(Causality::Output),
+            parameters: with_causality(Causality::Parameter),
+            locals: with_causality(Causality::Local),
+        }
+    }
+    
+    /// Iterate over every variable regardless of causality
+    pub fn variables(&self) -> impl Iterator<Item = &IOSpec> {
+        self.inputs.iter()
+            .chain(self.outputs.iter())
+            .chain(self.parameters.iter())
+            .chain(self.locals.iter())
+    }
+    
+    /// Find a variable by name
+    pub fn find(&self, name: &str) -> Option<&IOSpec> {
+        self.variables().find(|v| v.name == name)
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IOSpec {
     pub name: String,
+    #[serde(rename = "type")]
     pub io_type: IOType,
+    pub causality: Causality,
+    pub variability: Variability,
     pub unit: Option<String>,
+    /// Unit preferred for display, e.g. "degC" for a value stored in "K"
+    pub display_unit: Option<String>,
     pub description: Option<String>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub start: Option<f64>,
+    pub nominal: Option<f64>,
+}
+
+impl IOSpec {
+    /// Create a spec with no unit, bounds or start value
+    pub fn new(name: &str, io_type: IOType, causality: Causality, variability: Variability) -> Self {
+        Self {
+            name: name.to_string(),
+            io_type,
+            causality,
+            variability,
+            unit: None,
+            display_unit: None,
+            description: None,
+            min: None,
+            max: None,
+            start: None,
+            nominal: None,
+        }
+    }
+    
+    pub fn with_unit(mut self, unit: &str) -> Self {
+        self.unit = Some(unit.to_string());
+        self
+    }
+    
+    pub fn with_display_unit(mut self, display_unit: &str) -> Self {
+        self.display_unit = Some(display_unit.to_string());
+        self
+    }
+    
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+    
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+    
+    pub fn with_min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+    
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+    
+    pub fn with_start(mut self, start: f64) -> Self {
+        self.start = Some(start);
+        self
+    }
+    
+    pub fn with_nominal(mut self, nominal: f64) -> Self {
+        self.nominal = Some(nominal);
+        self
+    }
+    
+    /// Check a value against `min`/`max`
+    pub fn check_bounds(&self, value: f64) -> ComponentResult<()> {
+        let min = self.min.unwrap_or(f64::NEG_INFINITY);
+        let max = self.max.unwrap_or(f64::INFINITY);
+        if !(min..=max).contains(&value) {
+            return Err(ComponentError::BoundsCheckFailed(self.name.clone(), value, min, max));
+        }
+        Ok(())
+    }
+    
+    /// Check a value against `min`/`max` and, for integers, that it is whole
+    pub fn check_value(&self, value: f64) -> ComponentResult<()> {
+        if self.io_type == IOType::Integer && value.fract() != 0.0 {
+            return Err(ComponentError::InvalidInput(
+                format!("'{}' is an integer, got: {}", self.name, value)
+            ));
+        }
+        self.check_bounds(value)
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IOType {
     Real,
     Boolean,
     Integer,
-}
\ No newline at end of file
+}
+
+/// How a variable is visible from outside the model (FMI causality)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Causality {
+    Parameter,
+    Input,
+    Output,
+    /// States and intermediate variables
+    Local,
+    /// Simulation time
+    Independent,
+}
+
+/// When a variable may change value (FMI variability)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Variability {
+    /// Never changes
+    Constant,
+    /// Only before initialization (structural parameters, start values)
+    Fixed,
+    /// Any time, takes effect on the next step
+    Tunable,
+    /// Only at events
+    Discrete,
+    /// Any time during integration
+    Continuous,
+}
@@ -1 +1,2 @@
+pub mod modelica;
 pub mod simple_thermal;
\ No newline at end of file
@@ -0,0 +1,149 @@
+use crate::component::*;
+use crate::runtime::ModelicaRuntime;
+use std::collections::HashMap;
+
+/// Component backed by any model the `ModelicaRuntime` knows about
+///
+/// Metadata, `get_all_outputs` and input validation are all derived from
+/// the runtime's variable table, so adding a model needs no Rust wrapper.
+///
+/// # Examples
+///
+/// ```no_run
+/// use modelica_rust_ffi::{ModelicaComponent, SimulationComponent};
+///
+/// let mut thermal = ModelicaComponent::new("SimpleThermalMVP")?;
+/// thermal.initialize()?;
+/// thermal.set_bool_input("heaterOn", true)?;
+/// thermal.step(0.1)?;
+/// println!("{:?}", thermal.get_all_outputs());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct ModelicaComponent {
+    runtime: ModelicaRuntime,
+}
+
+impl ModelicaComponent {
+    pub fn new(model_name: &str) -> ComponentResult<Self> {
+        Ok(Self {
+            runtime: ModelicaRuntime::new(model_name)?,
+        })
+    }
+
+    /// Create a component with parameter overrides
+    pub fn with_parameters(model_name: &str, parameters: &HashMap<String, f64>) -> ComponentResult<Self> {
+        Ok(Self {
+            runtime: ModelicaRuntime::with_parameters(model_name, parameters.clone())?,
+        })
+    }
+
+    /// Wrap an existing runtime
+    pub fn from_runtime(runtime: ModelicaRuntime) -> Self {
+        Self { runtime }
+    }
+
+    /// Metadata for a model type, built from its static variable table
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::UnknownComponentType` for an unknown model.
+    pub fn type_metadata(model_name: &str) -> ComponentResult<ComponentMetadata> {
+        let (category, variables) = ModelicaRuntime::model_description(model_name)?;
+        Ok(ComponentMetadata::from_variables(model_name, category, variables))
+    }
+
+    /// Access the underlying runtime
+    pub fn runtime(&self) -> &ModelicaRuntime {
+        &self.runtime
+    }
+
+    /// Internal: Look up a variable that may be written from outside
+    ///
+    /// Inputs, parameters and states: writing a state (`Local`) sets its
+    /// initial condition, e.g. a scenario's `initial = { roomTemp = 300.0 }`,
+    /// as with `#[state]` fields of derived components.
+    fn writable_variable(&self, name: &str, io_type: IOType) -> ComponentResult<&IOSpec> {
+        let spec = self.runtime.variable(name)
+            .ok_or_else(|| ComponentError::VariableNotFound(name.to_string()))?;
+
+        if matches!(spec.causality, Causality::Output | Causality::Independent) {
+            return Err(ComponentError::InvalidInput(
+                format!("'{}' is computed by the model and cannot be set", name)
+            ));
+        }
+
+        let is_bool = spec.io_type == IOType::Boolean;
+        if is_bool != (io_type == IOType::Boolean) {
+            return Err(ComponentError::InvalidInput(
+                format!("'{}' is of type {:?}", name, spec.io_type)
+            ));
+        }
+
+        Ok(spec)
+    }
+}
+
+impl SimulationComponent for ModelicaComponent {
+    fn component_type(&self) -> &str {
+        self.runtime.component_name()
+    }
+
+    fn initialize(&mut self) -> ComponentResult<()> {
+        self.runtime.initialize()
+    }
+
+    fn set_input(&mut self, name: &str, value: f64) -> ComponentResult<()> {
+        if self.writable_variable(name, IOType::Real)?.causality == Causality::Parameter {
+            return self.runtime.set_parameter(name, value);
+        }
+        self.runtime.set_real_variable(name, value)
+    }
+
+    fn set_bool_input(&mut self, name: &str, value: bool) -> ComponentResult<()> {
+        self.writable_variable(name, IOType::Boolean)?;
+        self.runtime.set_bool_variable(name, value)
+    }
+
+    fn get_output(&self, name: &str) -> ComponentResult<f64> {
+        match self.runtime.variable(name).map(|spec| spec.io_type) {
+            Some(IOType::Boolean) => self.runtime.get_bool_variable(name)
+                .map(|b| if b { 1.0 } else { 0.0 }),
+            _ => self.runtime.get_real_variable(name),
+        }
+    }
+
+    fn step(&mut self, dt: f64) -> ComponentResult<()> {
+        self.runtime.step(dt)
+    }
+
+    fn reset(&mut self) -> ComponentResult<()> {
+        self.runtime.reset()
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f64) -> ComponentResult<()> {
+        self.runtime.set_parameter(name, value)
+    }
+
+    fn get_all_outputs(&self) -> HashMap<String, f64> {
+        self.runtime.variables().iter()
+            .filter(|spec| spec.causality == Causality::Output)
+            .filter_map(|spec| {
+                self.get_output(&spec.name).ok().map(|value| (spec.name.clone(), value))
+            })
+            .collect()
+    }
+
+    fn metadata(&self) -> ComponentMetadata {
+        ComponentMetadata::from_variables(
+            self.runtime.component_name(),
+            self.runtime.category(),
+            self.runtime.variables(),
+        )
+    }
+
+    fn solver_calls(&self) -> Option<u64> {
+        Some(self.runtime.solver_calls())
+    }
+}
+
@@ -1,88 +1,84 @@
 use crate::component::*;
-use crate::runtime::ModelicaRuntime;
+use crate::components::modelica::ModelicaComponent;
 use std::collections::HashMap;
 
+/// The SimpleThermalMVP model as a component
+///
+/// A thin wrapper around a generic `ModelicaComponent` that keeps the model
+/// name in one place.
 pub struct SimpleThermalComponent {
-    runtime: ModelicaRuntime,
+    component: ModelicaComponent,
 }
 
 impl SimpleThermalComponent {
+    pub const MODEL_NAME: &'static str = "SimpleThermalMVP";
+
     pub fn new() -> ComponentResult<Self> {
-        Ok(Self {
-            runtime: ModelicaRuntime::new("SimpleThermalMVP")?,
-        })
+        Ok(Self { component: ModelicaComponent::new(Self::MODEL_NAME)? })
+    }
+
+    /// Create a component with parameter overrides (e.g. `heaterPower`)
+    pub fn with_parameters(parameters: &HashMap<String, f64>) -> ComponentResult<Self> {
+        Ok(Self { component: ModelicaComponent::with_parameters(Self::MODEL_NAME, parameters)? })
+    }
+
+    /// Metadata for the SimpleThermalMVP type, available without an instance
+    pub fn type_metadata() -> ComponentResult<ComponentMetadata> {
+        ModelicaComponent::type_metadata(Self::MODEL_NAME)
+    }
+
+    /// The underlying generic component
+    pub fn component(&self) -> &ModelicaComponent {
+        &self.component
+    }
+
+    pub fn into_component(self) -> ModelicaComponent {
+        self.component
     }
 }
 
 impl SimulationComponent for SimpleThermalComponent {
     fn component_type(&self) -> &str {
-        "SimpleThermalMVP"
+        self.component.component_type()
     }
-    
+
     fn initialize(&mut self) -> ComponentResult<()> {
-        self.runtime.reset()
+        self.component.initialize()
     }
-    
+
     fn set_input(&mut self, name: &str, value: f64) -> ComponentResult<()> {
-        self.runtime.set_real_variable(name, value)
+        self.component.set_input(name, value)
     }
-    
+
     fn set_bool_input(&mut self, name: &str, value: bool) -> ComponentResult<()> {
-        self.runtime.set_bool_variable(name, value)
+        self.component.set_bool_input(name, value)
     }
-    
+
     fn get_output(&self, name: &str) -> ComponentResult<f64> {
-        self.runtime.get_real_variable(name)
+        self.component.get_output(name)
     }
-    
+
     fn step(&mut self, dt: f64) -> ComponentResult<()> {
-        self.runtime.step(dt)
+        self.component.step(dt)
     }
-    
+
     fn reset(&mut self) -> ComponentResult<()> {
-        self.runtime.reset()
+        self.component.reset()
     }
-    
+
+    fn set_parameter(&mut self, name: &str, value: f64) -> ComponentResult<()> {
+        self.component.set_parameter(name, value)
+    }
+
     fn get_all_outputs(&self) -> HashMap<String, f64> {
-        let mut outputs = HashMap::new();
-        if let Ok(temp) = self.runtime.get_real_variable("temperature") {
-            outputs.insert("temperature".to_string(), temp);
-        }
-        if let Ok(status) = self.runtime.get_real_variable("heaterStatus") {
-            outputs.insert("heaterStatus".to_string(), status);
-        }
-        outputs
-    }
-    
+        self.component.get_all_outputs()
+    }
+
     fn metadata(&self) -> ComponentMetadata {
-        ComponentMetadata {
-            name: "SimpleThermalMVP".to_string(),
-            component_type: "Thermal".to_string(),
-            inputs: vec![
-                IOSpec {
-                    name: "heaterOn".to_string(),
-                    io_type: IOType::Boolean,
-                    unit: None,
-                    description: Some("Heater control signal".to_string()),
-                }
-            ],
-            outputs: vec![
-                IOSpec {
-                    name: "temperature".to_string(),
-                    io_type: IOType::Real,
-                    unit: Some("K".to_string()),
-                    description: Some("Current room temperature".to_string()),
-                },
-                IOSpec {
-                    name: "heaterStatus".to_string(),
-                    io_type: IOType::Real,
-                    unit: None,
-                    description: Some("Heater status (0=off, 1=on)".to_string()),
-                }
-            ],
-        }
+        self.component.metadata()
     }
-}
 
-unsafe impl Send for SimpleThermalComponent {}
-unsafe impl Sync for SimpleThermalComponent {}
\ No newline at end of file
+    fn solver_calls(&self) -> Option<u64> {
+        self.component.solver_calls()
+    }
+}
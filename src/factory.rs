@@ -0,0 +1,127 @@
+use crate::component::{ComponentError, ComponentMetadata, ComponentResult, SimulationComponent};
+use crate::components::modelica::ModelicaComponent;
+use crate::runtime::ModelicaRuntime;
+use std::collections::HashMap;
+
+/// Constructor registered for a component type
+///
+/// Receives the parameter overrides requested by the caller and returns a
+/// new, uninitialized component.
+pub type ComponentConstructor =
+    Box<dyn Fn(&HashMap<String, f64>) -> ComponentResult<Box<dyn SimulationComponent>> + Send + Sync>;
+
+struct ComponentType {
+    metadata: ComponentMetadata,
+    constructor: ComponentConstructor,
+}
+
+/// Creates components from their type name
+///
+/// Component types register a constructor and their metadata once; scenario
+/// loaders, network APIs and plugins can then instantiate them from a string
+/// such as `"SimpleThermalMVP"` without depending on the concrete Rust type.
+///
+/// # Examples
+///
+/// ```no_run
+/// use modelica_rust_ffi::ComponentFactory;
+/// use std::collections::HashMap;
+///
+/// let factory = ComponentFactory::with_builtins();
+///
+/// let mut parameters = HashMap::new();
+/// parameters.insert("heaterPower".to_string(), 750.0);
+/// let thermal = factory.create("SimpleThermalMVP", &parameters)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ComponentFactory {
+    types: HashMap<String, ComponentType>,
+}
+
+impl ComponentFactory {
+    /// Creates an empty factory with no registered types
+    pub fn new() -> Self {
+        Self {
+            types: HashMap::new(),
+        }
+    }
+
+    /// Creates a factory with every model the `ModelicaRuntime` provides
+    pub fn with_builtins() -> Self {
+        let mut factory = Self::new();
+        for model_name in ModelicaRuntime::available_models() {
+            factory.register_modelica(model_name)
+                .expect("built-in component types are unique");
+        }
+        factory
+    }
+
+    /// Registers a Modelica model as a `ModelicaComponent` type
+    ///
+    /// Metadata is taken from the runtime's variable table.
+    pub fn register_modelica(&mut self, model_name: &str) -> ComponentResult<()> {
+        let metadata = ModelicaComponent::type_metadata(model_name)?;
+        let owned_name = model_name.to_string();
+        self.register(model_name, metadata, move |parameters| {
+            Ok(Box::new(ModelicaComponent::with_parameters(&owned_name, parameters)?))
+        })
+    }
+
+    /// Registers a component type
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::InitializationFailed` if a type with the same
+    /// name is already registered.
+    pub fn register<F>(&mut self, type_name: &str, metadata: ComponentMetadata, constructor: F) -> ComponentResult<()>
+    where
+        F: Fn(&HashMap<String, f64>) -> ComponentResult<Box<dyn SimulationComponent>> + Send + Sync + 'static,
+    {
+        if self.types.contains_key(type_name) {
+            return Err(ComponentError::InitializationFailed(
+                format!("Component type '{}' is already registered", type_name)
+            ));
+        }
+
+        self.types.insert(type_name.to_string(), ComponentType {
+            metadata,
+            constructor: Box::new(constructor),
+        });
+        Ok(())
+    }
+
+    /// Creates a new component of the given type
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::UnknownComponentType` if no such type is
+    /// registered, or whatever error the type's constructor reports.
+    pub fn create(&self, type_name: &str, parameters: &HashMap<String, f64>) -> ComponentResult<Box<dyn SimulationComponent>> {
+        let component_type = self.types.get(type_name)
+            .ok_or_else(|| ComponentError::UnknownComponentType(type_name.to_string()))?;
+        (component_type.constructor)(parameters)
+    }
+
+    /// Returns the metadata registered for a type
+    pub fn metadata(&self, type_name: &str) -> Option<&ComponentMetadata> {
+        self.types.get(type_name).map(|t| &t.metadata)
+    }
+
+    /// Returns true if the type is registered
+    pub fn contains(&self, type_name: &str) -> bool {
+        self.types.contains_key(type_name)
+    }
+
+    /// List all registered type names, sorted
+    pub fn list_types(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.types.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+impl Default for ComponentFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
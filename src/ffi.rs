@@ -0,0 +1,366 @@
+//! C API for hosting the simulation from C, C++ and other non-Rust code
+//!
+//! The registry is an opaque `MrfRegistry` handle created with
+//! `mrf_registry_new` and released with `mrf_registry_free`. Components
+//! are addressed by their instance name. Every fallible function returns
+//! an `MrfStatus`; on failure `mrf_last_error` describes what went wrong.
+//!
+//! Strings are NUL-terminated UTF-8 and are only borrowed for the duration
+//! of the call. Panics never cross the boundary: they are reported as
+//! `MRF_STATUS_PANIC`.
+//!
+//! Build with `--features c-header` to regenerate
+//! `include/modelica_rust_ffi.h`.
+
+use crate::component::{ComponentError, SimulationComponent};
+use crate::factory::ComponentFactory;
+use crate::registry::ComponentRegistry;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// Result of a C API call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MrfStatus {
+    Ok = 0,
+    /// A required pointer argument was NULL
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8
+    InvalidString = 2,
+    /// Unknown component, component type or variable
+    NotFound = 3,
+    /// Rejected value (type, bounds, causality or fixed parameter)
+    InvalidInput = 4,
+    /// A component failed to step
+    StepFailed = 5,
+    /// Any other component error
+    Error = 6,
+    /// The call panicked; the registry may be inconsistent
+    Panic = 7,
+}
+
+/// Opaque registry handle
+pub struct MrfRegistry {
+    registry: ComponentRegistry,
+    factory: ComponentFactory,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Internal: A failed call, recorded in `LAST_ERROR` before returning
+struct Failure {
+    status: MrfStatus,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: MrfStatus, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+}
+
+impl From<ComponentError> for Failure {
+    fn from(error: ComponentError) -> Self {
+        let status = match error {
+            ComponentError::VariableNotFound(_) | ComponentError::UnknownComponentType(_) => MrfStatus::NotFound,
+            ComponentError::InvalidInput(_)
+            | ComponentError::BoundsCheckFailed(..)
+            | ComponentError::FixedParameter(_) => MrfStatus::InvalidInput,
+            ComponentError::StepFailed(_) => MrfStatus::StepFailed,
+            _ => MrfStatus::Error,
+        };
+        Self::new(status, error.to_string())
+    }
+}
+
+fn set_last_error(message: String) {
+    // Interior NULs would truncate the message on the C side anyway
+    let message = CString::new(message.replace('\0', " ")).expect("NULs were removed");
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Internal: Runs `f`, recording errors and containing panics
+fn call<F>(f: F) -> MrfStatus
+where
+    F: FnOnce() -> Result<(), Failure>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => MrfStatus::Ok,
+        Ok(Err(failure)) => {
+            set_last_error(failure.message);
+            failure.status
+        }
+        Err(payload) => {
+            let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            set_last_error(format!("panic: {}", message));
+            MrfStatus::Panic
+        }
+    }
+}
+
+unsafe fn registry_arg<'a>(registry: *mut MrfRegistry) -> Result<&'a mut MrfRegistry, Failure> {
+    registry.as_mut().ok_or_else(|| Failure::new(MrfStatus::NullPointer, "registry is NULL"))
+}
+
+unsafe fn str_arg<'a>(ptr: *const c_char, what: &str) -> Result<&'a str, Failure> {
+    if ptr.is_null() {
+        return Err(Failure::new(MrfStatus::NullPointer, format!("{} is NULL", what)));
+    }
+    CStr::from_ptr(ptr).to_str()
+        .map_err(|_| Failure::new(MrfStatus::InvalidString, format!("{} is not valid UTF-8", what)))
+}
+
+impl MrfRegistry {
+    fn component(&mut self, name: &str) -> Result<&mut Box<dyn SimulationComponent>, Failure> {
+        self.registry.get_mut_by_name(name)
+            .ok_or_else(|| Failure::new(MrfStatus::NotFound, format!("Component '{}' not found", name)))
+    }
+
+    fn add(&mut self, type_name: &str, name: &str, parameters: &HashMap<String, f64>) -> Result<(), Failure> {
+        let mut component = self.factory.create(type_name, parameters)?;
+        component.initialize()?;
+        self.registry.add(name.to_string(), component)?;
+        Ok(())
+    }
+}
+
+/// Creates an empty registry that can instantiate every built-in model
+///
+/// Release it with `mrf_registry_free`.
+#[no_mangle]
+pub extern "C" fn mrf_registry_new() -> *mut MrfRegistry {
+    Box::into_raw(Box::new(MrfRegistry {
+        registry: ComponentRegistry::new(),
+        factory: ComponentFactory::with_builtins(),
+    }))
+}
+
+/// Destroys a registry and every component in it
+///
+/// # Safety
+///
+/// `registry` must be NULL or a handle from `mrf_registry_new` that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn mrf_registry_free(registry: *mut MrfRegistry) {
+    if !registry.is_null() {
+        drop(Box::from_raw(registry));
+    }
+}
+
+/// Creates and initializes a component of `type_name` called `name`
+///
+/// # Safety
+///
+/// `registry` must be a live handle; `type_name` and `name` must be
+/// NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn mrf_registry_add(
+    registry: *mut MrfRegistry,
+    type_name: *const c_char,
+    name: *const c_char,
+) -> MrfStatus {
+    mrf_registry_add_with_parameters(registry, type_name, name, ptr::null(), ptr::null(), 0)
+}
+
+/// Like `mrf_registry_add`, with `count` parameter overrides applied before
+/// initialization (so fixed parameters can be set too)
+///
+/// # Safety
+///
+/// As `mrf_registry_add`; in addition `parameter_names` and `values` must
+/// point to `count` elements each (they may be NULL when `count` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn mrf_registry_add_with_parameters(
+    registry: *mut MrfRegistry,
+    type_name: *const c_char,
+    name: *const c_char,
+    parameter_names: *const *const c_char,
+    values: *const f64,
+    count: usize,
+) -> MrfStatus {
+    call(|| {
+        let registry = registry_arg(registry)?;
+        let type_name = str_arg(type_name, "type_name")?;
+        let name = str_arg(name, "name")?;
+
+        let mut parameters = HashMap::new();
+        if count > 0 {
+            if parameter_names.is_null() || values.is_null() {
+                return Err(Failure::new(MrfStatus::NullPointer, "parameter arrays are NULL"));
+            }
+            let names = std::slice::from_raw_parts(parameter_names, count);
+            let values = std::slice::from_raw_parts(values, count);
+            for (&parameter, &value) in names.iter().zip(values) {
+                parameters.insert(str_arg(parameter, "parameter name")?.to_string(), value);
+            }
+        }
+
+        registry.add(type_name, name, &parameters)
+    })
+}
+
+/// Removes and destroys a component
+///
+/// # Safety
+///
+/// `registry` must be a live handle; `name` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn mrf_registry_remove(registry: *mut MrfRegistry, name: *const c_char) -> MrfStatus {
+    call(|| {
+        let registry = registry_arg(registry)?;
+        let name = str_arg(name, "name")?;
+        let id = registry.registry.id_by_name(name)
+            .ok_or_else(|| Failure::new(MrfStatus::NotFound, format!("Component '{}' not found", name)))?;
+        registry.registry.remove(id)?;
+        Ok(())
+    })
+}
+
+/// Number of components in the registry (0 for NULL)
+///
+/// # Safety
+///
+/// `registry` must be NULL or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn mrf_registry_len(registry: *const MrfRegistry) -> usize {
+    registry.as_ref().map_or(0, |r| r.registry.len())
+}
+
+/// Advances every component by `dt` seconds
+///
+/// # Safety
+///
+/// `registry` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn mrf_registry_step_all(registry: *mut MrfRegistry, dt: f64) -> MrfStatus {
+    call(|| {
+        registry_arg(registry)?.registry.step_all(dt)?;
+        Ok(())
+    })
+}
+
+/// Sets a real input (or state) of a component
+///
+/// # Safety
+///
+/// `registry` must be a live handle; `component` and `variable` must be
+/// NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn mrf_component_set_real(
+    registry: *mut MrfRegistry,
+    component: *const c_char,
+    variable: *const c_char,
+    value: f64,
+) -> MrfStatus {
+    call(|| {
+        let registry = registry_arg(registry)?;
+        let component = str_arg(component, "component")?;
+        let variable = str_arg(variable, "variable")?;
+        registry.component(component)?.set_input(variable, value)?;
+        Ok(())
+    })
+}
+
+/// Sets a boolean input of a component
+///
+/// # Safety
+///
+/// As `mrf_component_set_real`.
+#[no_mangle]
+pub unsafe extern "C" fn mrf_component_set_bool(
+    registry: *mut MrfRegistry,
+    component: *const c_char,
+    variable: *const c_char,
+    value: bool,
+) -> MrfStatus {
+    call(|| {
+        let registry = registry_arg(registry)?;
+        let component = str_arg(component, "component")?;
+        let variable = str_arg(variable, "variable")?;
+        registry.component(component)?.set_bool_input(variable, value)?;
+        Ok(())
+    })
+}
+
+/// Changes a parameter of a component
+///
+/// # Safety
+///
+/// As `mrf_component_set_real`.
+#[no_mangle]
+pub unsafe extern "C" fn mrf_component_set_parameter(
+    registry: *mut MrfRegistry,
+    component: *const c_char,
+    parameter: *const c_char,
+    value: f64,
+) -> MrfStatus {
+    call(|| {
+        let registry = registry_arg(registry)?;
+        let component = str_arg(component, "component")?;
+        let parameter = str_arg(parameter, "parameter")?;
+        registry.component(component)?.set_parameter(parameter, value)?;
+        Ok(())
+    })
+}
+
+/// Reads any variable of a component into `out` (booleans read as 0/1)
+///
+/// # Safety
+///
+/// As `mrf_component_set_real`; `out` must be valid for writing a double.
+#[no_mangle]
+pub unsafe extern "C" fn mrf_component_get_real(
+    registry: *mut MrfRegistry,
+    component: *const c_char,
+    variable: *const c_char,
+    out: *mut f64,
+) -> MrfStatus {
+    call(|| {
+        let registry = registry_arg(registry)?;
+        let component = str_arg(component, "component")?;
+        let variable = str_arg(variable, "variable")?;
+        let out = out.as_mut()
+            .ok_or_else(|| Failure::new(MrfStatus::NullPointer, "out is NULL"))?;
+        *out = registry.component(component)?.get_output(variable)?;
+        Ok(())
+    })
+}
+
+/// Resets a component to its start values
+///
+/// # Safety
+///
+/// `registry` must be a live handle; `component` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn mrf_component_reset(registry: *mut MrfRegistry, component: *const c_char) -> MrfStatus {
+    call(|| {
+        let registry = registry_arg(registry)?;
+        let component = str_arg(component, "component")?;
+        registry.component(component)?.reset()?;
+        Ok(())
+    })
+}
+
+/// Message for the last failed call on this thread, or NULL
+///
+/// The string stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn mrf_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Crate version as a static string
+#[no_mangle]
+pub extern "C" fn mrf_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
@@ -0,0 +1,217 @@
+//! Golden-trace regression tests
+//!
+//! A `GoldenTest` runs a scenario, records it like `modelica-sim run` and
+//! compares the result with a stored golden CSV. When outputs drift beyond
+//! the tolerance the test fails with a table of the first differing
+//! samples. Setting `BLESS=1` writes the current results as the new golden
+//! file instead:
+//!
+//! ```no_run
+//! use modelica_rust_ffi::golden::GoldenTest;
+//!
+//! #[test]
+//! fn habitat_heating() {
+//!     GoldenTest::new("tests/golden/habitat_heating.toml", "tests/golden/habitat_heating.csv")
+//!         .unwrap()
+//!         .assert();
+//! }
+//! ```
+//!
+//! ```text
+//! BLESS=1 cargo test --test golden_test   # regenerate, then review the CSV diff
+//! ```
+
+use crate::compare::{compare, Comparison, ResultSet, Tolerance};
+use crate::component::{ComponentError, ComponentResult};
+use crate::recorder::{CsvSink, Recorder};
+use crate::scenario::Scenario;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that switches golden tests to regenerating
+pub const BLESS_VAR: &str = "BLESS";
+
+/// Samples shown per drifting variable
+const DIFF_ROWS: usize = 5;
+
+/// A scenario replayed against a stored golden CSV
+pub struct GoldenTest {
+    scenario: Scenario,
+    source: String,
+    golden: PathBuf,
+    tolerance: Tolerance,
+    patterns: Vec<String>,
+    interval: Option<f64>,
+    bless: bool,
+}
+
+impl GoldenTest {
+    /// Loads the scenario file; blesses if `BLESS` is set to anything but `0`
+    pub fn new<P: AsRef<Path>, Q: AsRef<Path>>(scenario: P, golden: Q) -> ComponentResult<Self> {
+        let source = scenario.as_ref().display().to_string();
+        let mut test = Self::from_scenario(Scenario::from_file(scenario)?, golden);
+        test.source = source;
+        Ok(test)
+    }
+
+    /// Uses an in-memory scenario
+    pub fn from_scenario<Q: AsRef<Path>>(scenario: Scenario, golden: Q) -> Self {
+        let bless = std::env::var(BLESS_VAR).is_ok_and(|value| !value.is_empty() && value != "0");
+        Self {
+            scenario,
+            source: "<scenario>".to_string(),
+            golden: golden.as_ref().to_path_buf(),
+            tolerance: Tolerance::default(),
+            patterns: Vec::new(),
+            interval: None,
+            bless,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Records only variables matching `pattern` (default: every output)
+    pub fn select(mut self, pattern: &str) -> Self {
+        self.patterns.push(pattern.to_string());
+        self
+    }
+
+    /// Records at most one row every `interval` seconds
+    pub fn with_interval(mut self, interval: f64) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Overrides the `BLESS` environment variable
+    pub fn bless(mut self, bless: bool) -> Self {
+        self.bless = bless;
+        self
+    }
+
+    /// Runs the scenario and returns the recorded CSV
+    pub fn record(&self) -> ComponentResult<String> {
+        let mut recorder = Recorder::new(CsvSink::new(Vec::new()));
+        for pattern in &self.patterns {
+            recorder = recorder.select(pattern);
+        }
+        if let Some(interval) = self.interval {
+            recorder = recorder.with_interval(interval);
+        }
+
+        let mut registry = self.scenario.build_registry()?;
+        recorder.start(&registry)?;
+        self.scenario.run_with(&mut registry, |time, registry| recorder.sample(time, registry))?;
+
+        let bytes = recorder.into_sink()?.into_inner();
+        String::from_utf8(bytes).map_err(|e| ComponentError::RecordingFailed(e.to_string()))
+    }
+
+    /// Runs the scenario and compares it with (or, blessing, writes) the golden file
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::InvalidResults` if the golden file is missing
+    /// or unreadable, and any error from building or running the scenario.
+    /// Drift is not an error; check `GoldenReport::passed`.
+    pub fn verify(&self) -> ComponentResult<GoldenReport> {
+        let csv = self.record()?;
+        let actual = ResultSet::from_csv_str(&csv)?;
+
+        if self.bless {
+            if let Some(dir) = self.golden.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir).map_err(|e| golden_error(&self.golden, e))?;
+            }
+            std::fs::write(&self.golden, &csv).map_err(|e| golden_error(&self.golden, e))?;
+        }
+
+        let golden = ResultSet::load(&self.golden)?;
+        let comparison = compare(&golden, &actual, self.tolerance)?;
+        let extra = actual.names()
+            .filter(|name| golden.values(name).is_none())
+            .map(str::to_string)
+            .collect();
+
+        Ok(GoldenReport {
+            source: self.source.clone(),
+            golden_path: self.golden.clone(),
+            blessed: self.bless,
+            comparison,
+            extra,
+            golden,
+            actual,
+        })
+    }
+
+    /// Like `verify`, but panics with a readable diff on drift or error
+    #[track_caller]
+    pub fn assert(&self) {
+        match self.verify() {
+            Ok(report) if report.passed() => {}
+            Ok(report) => panic!("\n{}", report),
+            Err(e) => panic!(
+                "golden test for {} failed: {}\n(run with {}=1 to create {})",
+                self.source, e, BLESS_VAR, self.golden.display()
+            ),
+        }
+    }
+}
+
+fn golden_error(path: &Path, e: std::io::Error) -> ComponentError {
+    ComponentError::InvalidResults(format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Outcome of a `GoldenTest`
+pub struct GoldenReport {
+    source: String,
+    golden_path: PathBuf,
+    pub blessed: bool,
+    pub comparison: Comparison,
+    /// Recorded variables the golden file lacks
+    pub extra: Vec<String>,
+    golden: ResultSet,
+    actual: ResultSet,
+}
+
+impl GoldenReport {
+    /// True if the recording matches the golden file within tolerance
+    pub fn passed(&self) -> bool {
+        self.comparison.passed() && self.extra.is_empty()
+    }
+}
+
+impl fmt::Display for GoldenReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.passed() { "matches" } else { "drifted from" };
+        writeln!(f, "{} {} {}", self.source, verdict, self.golden_path.display())?;
+        writeln!(f, "{}", self.comparison)?;
+
+        for diff in self.comparison.failures() {
+            let Some(first) = diff.first_violation else { continue };
+            writeln!(f, "\n{}:", diff.name)?;
+            writeln!(f, "  {:>12} {:>20} {:>20} {:>12}", "time", "golden", "actual", "diff")?;
+
+            let rows = self.golden.time().iter()
+                .filter(|&&time| time >= first)
+                .take(DIFF_ROWS);
+            for &time in rows {
+                let (Some(expected), Some(value)) =
+                    (self.golden.value_at(&diff.name, time), self.actual.value_at(&diff.name, time))
+                else {
+                    continue;
+                };
+                writeln!(f, "  {:>12} {:>20} {:>20} {:>+12.4e}", time, expected, value, value - expected)?;
+            }
+        }
+
+        if !self.extra.is_empty() {
+            writeln!(f, "\nNot in the golden file: {}", self.extra.join(", "))?;
+        }
+        if !self.passed() {
+            write!(f, "\nIf the change is intended, rerun with {}=1 to regenerate the golden file.", BLESS_VAR)?;
+        }
+        Ok(())
+    }
+}
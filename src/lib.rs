@@ -2,15 +2,40 @@
 #![allow(non_snake_case)]
 #![allow(non_upper_case_globals)]
 
+// Lets `#[derive(SimulationComponent)]` refer to `::modelica_rust_ffi` from inside this crate too
+extern crate self as modelica_rust_ffi;
+
 pub mod component;
 pub mod registry;
 pub mod runtime;  // Add this
 pub mod components;
+pub mod factory;
+pub mod ffi;
+pub mod models;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod scenario;
+pub mod recorder;
+pub mod compare;
+pub mod golden;
+pub mod observer;
+pub mod alarm;
+pub mod stats;
 
-pub use component::{SimulationComponent, ComponentError, ComponentResult, ComponentMetadata, IOSpec, IOType};
+pub use modelica_rust_ffi_derive::SimulationComponent;
+pub use component::{SimulationComponent, ComponentError, ComponentResult, ComponentMetadata, IOSpec, IOType, Causality, Variability};
 pub use registry::ComponentRegistry;
 pub use runtime::ModelicaRuntime;  // Add this
+pub use components::modelica::ModelicaComponent;
 pub use components::simple_thermal::SimpleThermalComponent;
+pub use models::VariableHandle;
+pub use factory::{ComponentFactory, ComponentConstructor};
+pub use recorder::{Recorder, RecordSource, ResultSink, Column, CsvSink, MatSink};
+#[cfg(feature = "arrow")]
+pub use recorder::{ArrowSink, ParquetSink};
+pub use observer::{Observable, Observers, ObserverId, StepInfo, Event, ThresholdCrossing, Direction, StepFailure};
+pub use stats::{RegistryStats, StepStats};
+pub use scenario::{Scenario, ComponentSpec, Connection, ScheduledInput, ScenarioValue};
 
 #[cfg(test)]
 mod tests {
@@ -51,4 +76,37 @@ mod tests {
         registry.remove(id).unwrap();
         assert!(registry.get_by_name("thermal_1").is_none());
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_factory() {
+        let factory = ComponentFactory::with_builtins();
+        assert_eq!(factory.list_types(), vec!["SimpleThermalMVP".to_string()]);
+        assert_eq!(factory.metadata("SimpleThermalMVP").unwrap().outputs.len(), 2);
+        
+        // Create with a parameter override
+        let mut parameters = std::collections::HashMap::new();
+        parameters.insert("heaterPower".to_string(), 1000.0);
+        let mut component = factory.create("SimpleThermalMVP", &parameters).unwrap();
+        component.initialize().unwrap();
+        assert_eq!(component.component_type(), "SimpleThermalMVP");
+        
+        // Unknown types are reported as such
+        let result = factory.create("NoSuchModel", &parameters);
+        assert!(matches!(result, Err(ComponentError::UnknownComponentType(_))));
+    }
+    
+    #[test]
+    fn test_factory_register() {
+        let mut factory = ComponentFactory::new();
+        factory.register("Thermal", SimpleThermalComponent::type_metadata().unwrap(), |_| {
+            Ok(Box::new(SimpleThermalComponent::new()?))
+        }).unwrap();
+        assert!(factory.contains("Thermal"));
+        
+        // Duplicate registration is rejected
+        let result = factory.register("Thermal", SimpleThermalComponent::type_metadata().unwrap(), |_| {
+            Ok(Box::new(SimpleThermalComponent::new()?))
+        });
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,30 @@
+//! Typed wrappers generated from the model descriptions in `models/`
+//!
+//! Each model gets a struct named after it, with a `VariableHandle`
+//! constant and typed getters/setters per variable:
+//!
+//! ```no_run
+//! use modelica_rust_ffi::models::SimpleThermalMVP;
+//! use modelica_rust_ffi::SimulationComponent;
+//!
+//! let mut thermal = SimpleThermalMVP::new()?;
+//! thermal.initialize()?;
+//! thermal.set_heater_on(true)?;
+//! thermal.step(0.1)?;
+//! println!("{} {}", thermal.temperature(), SimpleThermalMVP::TEMPERATURE.unit.unwrap());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::component::{Causality, ComponentMetadata, ComponentResult, SimulationComponent};
+use crate::components::modelica::ModelicaComponent;
+use std::collections::HashMap;
+
+/// Compile-time checked reference to a model variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariableHandle {
+    pub name: &'static str,
+    pub unit: Option<&'static str>,
+    pub causality: Causality,
+}
+
+include!(concat!(env!("OUT_DIR"), "/typed_models.rs"));
@@ -0,0 +1,352 @@
+//! Callbacks on simulation progress
+//!
+//! `ComponentRegistry` and `ModelicaRuntime` implement `Observable`, so a
+//! UI or alarm system can register callbacks instead of polling every
+//! output after each step:
+//!
+//! ```no_run
+//! use modelica_rust_ffi::{ComponentRegistry, Observable};
+//!
+//! # let mut registry = ComponentRegistry::new();
+//! registry.on_after_step(|registry, step| {
+//!     println!("t = {} s, {} components", step.time, registry.len());
+//! });
+//! registry.on_event(|_, event| {
+//!     println!("{} changed to {} at t = {} s", event.variable, event.value, event.time);
+//! });
+//! registry.on_threshold("habitat_1.temperature", 300.0, |_, crossing| {
+//!     println!("{} crossed 300 K ({:?})", crossing.variable, crossing.direction);
+//! })?;
+//! registry.on_error(|failure| eprintln!("step failed: {}", failure.error));
+//! # Ok::<(), modelica_rust_ffi::ComponentError>(())
+//! ```
+//!
+//! Callbacks run synchronously inside `step` / `step_all`, in registration
+//! order.
+
+use crate::component::{ComponentError, ComponentResult, Variability};
+use crate::recorder::RecordSource;
+
+/// Handle for removing a registered callback
+pub type ObserverId = u64;
+
+/// A step about to be taken, or just completed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepInfo {
+    /// Start time before the step, end time after it
+    pub time: f64,
+    pub dt: f64,
+}
+
+/// A discrete variable changed value during a step
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub time: f64,
+    pub variable: String,
+    pub previous: f64,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Rising,
+    Falling,
+}
+
+/// A variable crossed a threshold during a step
+///
+/// Rising means `previous < threshold <= value`, falling
+/// `previous >= threshold > value`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdCrossing {
+    pub time: f64,
+    pub variable: String,
+    pub threshold: f64,
+    pub direction: Direction,
+    pub previous: f64,
+    pub value: f64,
+}
+
+/// A step that failed
+#[derive(Debug)]
+pub struct StepFailure<'a> {
+    /// Start time of the failed step
+    pub time: f64,
+    /// The component that failed, when stepping a registry
+    pub component: Option<&'a str>,
+    pub error: &'a ComponentError,
+}
+
+type StepCallback<S> = Box<dyn FnMut(&S, &StepInfo) + Send + Sync>;
+type EventCallback<S> = Box<dyn FnMut(&S, &Event) + Send + Sync>;
+type ThresholdCallback<S> = Box<dyn FnMut(&S, &ThresholdCrossing) + Send + Sync>;
+type ErrorCallback = Box<dyn FnMut(&StepFailure) + Send + Sync>;
+
+struct Threshold<S> {
+    variable: String,
+    level: f64,
+    callback: ThresholdCallback<S>,
+    /// Value before the current step
+    previous: f64,
+}
+
+/// Callbacks registered on an `Observable` source `S`
+pub struct Observers<S> {
+    next_id: ObserverId,
+    before_step: Vec<(ObserverId, StepCallback<S>)>,
+    after_step: Vec<(ObserverId, StepCallback<S>)>,
+    events: Vec<(ObserverId, EventCallback<S>)>,
+    thresholds: Vec<(ObserverId, Threshold<S>)>,
+    errors: Vec<(ObserverId, ErrorCallback)>,
+    /// Discrete variables and their values before the current step
+    discrete: Vec<(String, f64)>,
+}
+
+impl<S> Observers<S> {
+    pub fn new() -> Self {
+        Self {
+            next_id: 0,
+            before_step: Vec::new(),
+            after_step: Vec::new(),
+            events: Vec::new(),
+            thresholds: Vec::new(),
+            errors: Vec::new(),
+            discrete: Vec::new(),
+        }
+    }
+
+    /// True if no callbacks are registered
+    pub fn is_empty(&self) -> bool {
+        self.before_step.is_empty()
+            && self.after_step.is_empty()
+            && self.events.is_empty()
+            && self.thresholds.is_empty()
+            && self.errors.is_empty()
+    }
+
+    /// Removes a callback; false if `id` is unknown
+    pub fn remove(&mut self, id: ObserverId) -> bool {
+        let before = self.len();
+        self.before_step.retain(|(i, _)| *i != id);
+        self.after_step.retain(|(i, _)| *i != id);
+        self.events.retain(|(i, _)| *i != id);
+        self.thresholds.retain(|(i, _)| *i != id);
+        self.errors.retain(|(i, _)| *i != id);
+        self.len() < before
+    }
+
+    /// Removes every callback
+    ///
+    /// Ids keep counting up, so ids handed out before stay invalid.
+    pub fn clear(&mut self) {
+        self.before_step.clear();
+        self.after_step.clear();
+        self.events.clear();
+        self.thresholds.clear();
+        self.errors.clear();
+        self.discrete.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.before_step.len() + self.after_step.len() + self.events.len() + self.thresholds.len() + self.errors.len()
+    }
+
+    fn next_id(&mut self) -> ObserverId {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+impl<S> Default for Observers<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: RecordSource> Observers<S> {
+    /// Internal: Snapshots watched values and runs the before-step callbacks
+    fn before(&mut self, source: &S, step: &StepInfo) {
+        for (_, callback) in &mut self.before_step {
+            callback(source, step);
+        }
+
+        self.discrete.clear();
+        if !self.events.is_empty() {
+            for spec in source.recordable_variables() {
+                if spec.variability != Variability::Discrete {
+                    continue;
+                }
+                if let Ok(value) = source.read_variable(&spec.name) {
+                    self.discrete.push((spec.name, value));
+                }
+            }
+        }
+        for (_, threshold) in &mut self.thresholds {
+            threshold.previous = source.read_variable(&threshold.variable).unwrap_or(f64::NAN);
+        }
+    }
+
+    /// Internal: Reports events and crossings, then runs the after-step callbacks
+    fn after(&mut self, source: &S, step: &StepInfo) {
+        for (variable, previous) in &self.discrete {
+            let Ok(value) = source.read_variable(variable) else { continue };
+            if value == *previous {
+                continue;
+            }
+            let event = Event { time: step.time, variable: variable.clone(), previous: *previous, value };
+            for (_, callback) in &mut self.events {
+                callback(source, &event);
+            }
+        }
+
+        for (_, threshold) in &mut self.thresholds {
+            let Ok(value) = source.read_variable(&threshold.variable) else { continue };
+            let previous = threshold.previous;
+            let direction = if previous < threshold.level && value >= threshold.level {
+                Direction::Rising
+            } else if previous >= threshold.level && value < threshold.level {
+                Direction::Falling
+            } else {
+                continue;
+            };
+            let crossing = ThresholdCrossing {
+                time: step.time,
+                variable: threshold.variable.clone(),
+                threshold: threshold.level,
+                direction,
+                previous,
+                value,
+            };
+            (threshold.callback)(source, &crossing);
+        }
+
+        for (_, callback) in &mut self.after_step {
+            callback(source, step);
+        }
+    }
+
+    fn failed(&mut self, failure: &StepFailure) {
+        for (_, callback) in &mut self.errors {
+            callback(failure);
+        }
+    }
+}
+
+/// A simulation that reports its progress to registered callbacks
+///
+/// Implemented by `ComponentRegistry` (variables named
+/// `"component.variable"`) and `ModelicaRuntime`.
+pub trait Observable: RecordSource + Sized {
+    fn observers(&mut self) -> &mut Observers<Self>;
+
+    /// Called with the start time before every step
+    fn on_before_step<F>(&mut self, callback: F) -> ObserverId
+    where
+        F: FnMut(&Self, &StepInfo) + Send + Sync + 'static,
+    {
+        let observers = self.observers();
+        let id = observers.next_id();
+        observers.before_step.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Called with the end time after every successful step
+    fn on_after_step<F>(&mut self, callback: F) -> ObserverId
+    where
+        F: FnMut(&Self, &StepInfo) + Send + Sync + 'static,
+    {
+        let observers = self.observers();
+        let id = observers.next_id();
+        observers.after_step.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Called when a discrete variable (e.g. `heaterStatus`) changes in a step
+    fn on_event<F>(&mut self, callback: F) -> ObserverId
+    where
+        F: FnMut(&Self, &Event) + Send + Sync + 'static,
+    {
+        let observers = self.observers();
+        let id = observers.next_id();
+        observers.events.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Called when `variable` crosses `threshold` in either direction
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::VariableNotFound` if `variable` can't be read.
+    fn on_threshold<F>(&mut self, variable: &str, threshold: f64, callback: F) -> ComponentResult<ObserverId>
+    where
+        F: FnMut(&Self, &ThresholdCrossing) + Send + Sync + 'static,
+    {
+        let previous = self.read_variable(variable)?;
+        let observers = self.observers();
+        let id = observers.next_id();
+        observers.thresholds.push((id, Threshold {
+            variable: variable.to_string(),
+            level: threshold,
+            callback: Box::new(callback),
+            previous,
+        }));
+        Ok(id)
+    }
+
+    /// Called when a step fails, before the error is returned
+    fn on_error<F>(&mut self, callback: F) -> ObserverId
+    where
+        F: FnMut(&StepFailure) + Send + Sync + 'static,
+    {
+        let observers = self.observers();
+        let id = observers.next_id();
+        observers.errors.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Unregisters a callback; false if `id` is unknown
+    fn remove_observer(&mut self, id: ObserverId) -> bool {
+        self.observers().remove(id)
+    }
+}
+
+/// Internal: Runs `step` on `source`, notifying its observers around it
+///
+/// `step` returns the failing component's name with the error, if known.
+pub(crate) fn observe_step<S, F>(source: &mut S, time: f64, dt: f64, step: F) -> ComponentResult<()>
+where
+    S: Observable,
+    F: FnOnce(&mut S) -> Result<(), (Option<String>, ComponentError)>,
+{
+    if source.observers().is_empty() {
+        return step(source).map_err(|(_, error)| error);
+    }
+
+    /// Puts the observers back, also when a callback or the step panics
+    struct Restore<'a, S: Observable> {
+        source: &'a mut S,
+        observers: Observers<S>,
+    }
+    impl<S: Observable> Drop for Restore<'_, S> {
+        fn drop(&mut self) {
+            *self.source.observers() = std::mem::take(&mut self.observers);
+        }
+    }
+
+    // Taken out so callbacks can borrow the source
+    let mut restore = Restore { observers: std::mem::take(source.observers()), source };
+    let Restore { source, observers } = &mut restore;
+    observers.before(source, &StepInfo { time, dt });
+
+    let result = step(source);
+    match &result {
+        Ok(()) => observers.after(source, &StepInfo { time: time + dt, dt }),
+        Err((component, error)) => observers.failed(&StepFailure {
+            time,
+            component: component.as_deref(),
+            error,
+        }),
+    }
+
+    result.map_err(|(_, error)| error)
+}
@@ -0,0 +1,532 @@
+//! Python bindings (`python` feature)
+//!
+//! Builds the `modelica_rust_ffi` extension module with `maturin develop
+//! --features python`. Results come back as NumPy arrays so parameter
+//! sweeps and batch reads don't cross the language boundary per sample:
+//!
+//! ```python
+//! import numpy as np
+//! import modelica_rust_ffi as mrf
+//!
+//! powers = np.linspace(250.0, 1000.0, 16)
+//! temps = mrf.sweep("SimpleThermalMVP", "heaterPower", powers, "temperature",
+//!                   stop_time=3600.0, step_size=1.0, initial={"heaterOn": True})
+//! print(temps.shape)  # (16, 3601)
+//! ```
+
+use crate::component::{ComponentError, ComponentMetadata, IOSpec, IOType, SimulationComponent};
+use crate::factory::ComponentFactory;
+use crate::registry::ComponentRegistry;
+use crate::runtime::ModelicaRuntime;
+use crate::stats::StepStats;
+use numpy::{PyArray1, PyArray2};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict};
+use std::collections::HashMap;
+
+create_exception!(modelica_rust_ffi, SimulationError, PyRuntimeError, "A component failed to initialize or step");
+
+impl From<ComponentError> for PyErr {
+    fn from(error: ComponentError) -> Self {
+        match error {
+            ComponentError::VariableNotFound(_) | ComponentError::UnknownComponentType(_) => {
+                PyKeyError::new_err(error.to_string())
+            }
+            ComponentError::InvalidInput(_)
+            | ComponentError::BoundsCheckFailed(..)
+            | ComponentError::FixedParameter(_)
+            | ComponentError::InvalidScenario(_)
+            | ComponentError::InvalidResults(_) => PyValueError::new_err(error.to_string()),
+            _ => SimulationError::new_err(error.to_string()),
+        }
+    }
+}
+
+/// A value passed in from Python: `True`/`False` or a number
+#[derive(Clone, Copy)]
+enum Value {
+    Bool(bool),
+    Real(f64),
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Value {
+    type Error = PyErr;
+
+    fn extract(value: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        // bool is a subclass of int, so check it first
+        if value.is_instance_of::<PyBool>() {
+            Ok(Value::Bool(value.extract()?))
+        } else {
+            Ok(Value::Real(value.extract()?))
+        }
+    }
+}
+
+fn set_value(component: &mut dyn SimulationComponent, name: &str, value: Value) -> PyResult<()> {
+    match value {
+        Value::Bool(b) => component.set_bool_input(name, b)?,
+        Value::Real(v) => component.set_input(name, v)?,
+    }
+    Ok(())
+}
+
+fn spec_to_dict<'py>(py: Python<'py>, spec: &IOSpec) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("name", &spec.name)?;
+    dict.set_item("type", format!("{:?}", spec.io_type))?;
+    dict.set_item("causality", format!("{:?}", spec.causality).to_lowercase())?;
+    dict.set_item("variability", format!("{:?}", spec.variability).to_lowercase())?;
+    dict.set_item("unit", &spec.unit)?;
+    dict.set_item("display_unit", &spec.display_unit)?;
+    dict.set_item("description", &spec.description)?;
+    dict.set_item("min", spec.min)?;
+    dict.set_item("max", spec.max)?;
+    dict.set_item("start", spec.start)?;
+    dict.set_item("nominal", spec.nominal)?;
+    Ok(dict)
+}
+
+/// Durations as seconds
+fn stats_to_dict<'py>(py: Python<'py>, stats: &StepStats) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("steps", stats.steps)?;
+    dict.set_item("total", stats.total.as_secs_f64())?;
+    dict.set_item("min", stats.min.as_secs_f64())?;
+    dict.set_item("mean", stats.mean.as_secs_f64())?;
+    dict.set_item("max", stats.max.as_secs_f64())?;
+    dict.set_item("p99", stats.p99.as_secs_f64())?;
+    dict.set_item("solver_calls", stats.solver_calls)?;
+    dict.set_item("allocations", stats.allocations)?;
+    Ok(dict)
+}
+
+fn metadata_to_dict<'py>(py: Python<'py>, metadata: &ComponentMetadata) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("name", &metadata.name)?;
+    dict.set_item("component_type", &metadata.component_type)?;
+    for (key, specs) in [
+        ("inputs", &metadata.inputs),
+        ("outputs", &metadata.outputs),
+        ("parameters", &metadata.parameters),
+        ("locals", &metadata.locals),
+    ] {
+        let list = specs.iter()
+            .map(|spec| spec_to_dict(py, spec))
+            .collect::<PyResult<Vec<_>>>()?;
+        dict.set_item(key, list)?;
+    }
+    Ok(dict)
+}
+
+/// Turns recorded columns into `{"time": array, name: array, ...}`
+fn columns_to_dict<'py>(py: Python<'py>, columns: Vec<(String, Vec<f64>)>) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (name, values) in columns {
+        dict.set_item(name, PyArray1::from_vec(py, values))?;
+    }
+    Ok(dict)
+}
+
+fn step_count(stop_time: f64, step_size: f64) -> PyResult<usize> {
+    if step_size <= 0.0 || !step_size.is_finite() || stop_time < 0.0 || !stop_time.is_finite() {
+        return Err(PyValueError::new_err(format!(
+            "Invalid step_size {} or stop_time {}", step_size, stop_time
+        )));
+    }
+    Ok((stop_time / step_size).round() as usize)
+}
+
+/// Low-level access to one model's variables
+#[pyclass(name = "ModelicaRuntime", module = "modelica_rust_ffi")]
+struct PyModelicaRuntime {
+    runtime: ModelicaRuntime,
+}
+
+#[pymethods]
+impl PyModelicaRuntime {
+    #[new]
+    #[pyo3(signature = (model_name, parameters = None))]
+    fn new(model_name: &str, parameters: Option<HashMap<String, f64>>) -> PyResult<Self> {
+        let runtime = match parameters {
+            Some(parameters) => ModelicaRuntime::with_parameters(model_name, parameters)?,
+            None => ModelicaRuntime::new(model_name)?,
+        };
+        Ok(Self { runtime })
+    }
+
+    /// Names of the models this build can run
+    #[staticmethod]
+    fn available_models() -> Vec<&'static str> {
+        ModelicaRuntime::available_models()
+    }
+
+    fn initialize(&mut self) -> PyResult<()> {
+        Ok(self.runtime.initialize()?)
+    }
+
+    fn step(&mut self, dt: f64) -> PyResult<()> {
+        Ok(self.runtime.step(dt)?)
+    }
+
+    fn reset(&mut self) -> PyResult<()> {
+        Ok(self.runtime.reset()?)
+    }
+
+    #[getter]
+    fn time(&self) -> f64 {
+        self.runtime.time()
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        self.runtime.component_name()
+    }
+
+    #[getter]
+    fn initialized(&self) -> bool {
+        self.runtime.is_initialized()
+    }
+
+    /// Reads any variable; booleans are returned as `bool`
+    fn get(&self, py: Python<'_>, name: &str) -> PyResult<Py<PyAny>> {
+        match self.runtime.variable(name).map(|spec| spec.io_type) {
+            Some(IOType::Boolean) => Ok(self.runtime.get_bool_variable(name)?.into_pyobject(py)?.to_owned().into_any().unbind()),
+            _ => Ok(self.runtime.get_real_variable(name)?.into_pyobject(py)?.into_any().unbind()),
+        }
+    }
+
+    fn set(&mut self, name: &str, value: Value) -> PyResult<()> {
+        match value {
+            Value::Bool(b) => self.runtime.set_bool_variable(name, b)?,
+            Value::Real(v) => self.runtime.set_real_variable(name, v)?,
+        }
+        Ok(())
+    }
+
+    fn get_parameter(&self, name: &str) -> PyResult<f64> {
+        Ok(self.runtime.get_parameter(name)?)
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f64) -> PyResult<()> {
+        Ok(self.runtime.set_parameter(name, value)?)
+    }
+
+    /// Current parameter values by name
+    fn parameters(&self) -> HashMap<String, f64> {
+        self.runtime.parameters()
+            .filter_map(|spec| {
+                self.runtime.get_parameter(&spec.name).ok().map(|value| (spec.name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// The variable table as a list of dicts
+    fn variables<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.runtime.variables().iter().map(|spec| spec_to_dict(py, spec)).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ModelicaRuntime({:?}, time={})", self.runtime.component_name(), self.runtime.time())
+    }
+}
+
+/// A single component created by type name
+#[pyclass(name = "Component", module = "modelica_rust_ffi")]
+struct PyComponent {
+    component: Box<dyn SimulationComponent>,
+}
+
+#[pymethods]
+impl PyComponent {
+    #[new]
+    #[pyo3(signature = (type_name, parameters = None))]
+    fn new(type_name: &str, parameters: Option<HashMap<String, f64>>) -> PyResult<Self> {
+        let factory = ComponentFactory::with_builtins();
+        let component = factory.create(type_name, &parameters.unwrap_or_default())?;
+        Ok(Self { component })
+    }
+
+    #[getter]
+    fn component_type(&self) -> String {
+        self.component.component_type().to_string()
+    }
+
+    fn initialize(&mut self) -> PyResult<()> {
+        Ok(self.component.initialize()?)
+    }
+
+    fn step(&mut self, dt: f64) -> PyResult<()> {
+        Ok(self.component.step(dt)?)
+    }
+
+    fn reset(&mut self) -> PyResult<()> {
+        Ok(self.component.reset()?)
+    }
+
+    fn get(&self, name: &str) -> PyResult<f64> {
+        Ok(self.component.get_output(name)?)
+    }
+
+    fn set(&mut self, name: &str, value: Value) -> PyResult<()> {
+        set_value(self.component.as_mut(), name, value)
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f64) -> PyResult<()> {
+        Ok(self.component.set_parameter(name, value)?)
+    }
+
+    fn outputs(&self) -> HashMap<String, f64> {
+        self.component.get_all_outputs()
+    }
+
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        metadata_to_dict(py, &self.component.metadata())
+    }
+
+    /// Steps to `stop_time` and returns `{"time": array, variable: array, ...}`
+    ///
+    /// Records every output unless `record` lists the variables to keep.
+    #[pyo3(signature = (stop_time, step_size, record = None))]
+    fn simulate<'py>(
+        &mut self,
+        py: Python<'py>,
+        stop_time: f64,
+        step_size: f64,
+        record: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let steps = step_count(stop_time, step_size)?;
+        let names = record.unwrap_or_else(|| {
+            self.component.metadata().outputs.into_iter().map(|spec| spec.name).collect()
+        });
+
+        let mut time = Vec::with_capacity(steps + 1);
+        let mut columns: Vec<Vec<f64>> = vec![Vec::with_capacity(steps + 1); names.len()];
+        let start = self.component.get_output("time").unwrap_or(0.0);
+
+        for step in 0..=steps {
+            if step > 0 {
+                self.component.step(step_size)?;
+            }
+            time.push(start + step as f64 * step_size);
+            for (name, column) in names.iter().zip(&mut columns) {
+                column.push(self.component.get_output(name)?);
+            }
+        }
+
+        let mut result = vec![("time".to_string(), time)];
+        result.extend(names.into_iter().zip(columns));
+        columns_to_dict(py, result)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Component({:?})", self.component.component_type())
+    }
+}
+
+/// A set of named components stepped together
+#[pyclass(name = "Registry", module = "modelica_rust_ffi")]
+struct PyRegistry {
+    registry: ComponentRegistry,
+    factory: ComponentFactory,
+}
+
+impl PyRegistry {
+    fn component(&self, name: &str) -> PyResult<&dyn SimulationComponent> {
+        self.registry.get_by_name(name)
+            .ok_or_else(|| PyKeyError::new_err(format!("Component '{}' not found", name)))
+    }
+
+    fn component_mut(&mut self, name: &str) -> PyResult<&mut Box<dyn SimulationComponent>> {
+        self.registry.get_mut_by_name(name)
+            .ok_or_else(|| PyKeyError::new_err(format!("Component '{}' not found", name)))
+    }
+
+    /// Reads `"component.variable"`
+    fn read_target(&self, target: &str) -> PyResult<f64> {
+        let (component, variable) = target.split_once('.')
+            .ok_or_else(|| PyValueError::new_err(format!("Expected 'component.variable', got '{}'", target)))?;
+        Ok(self.component(component)?.get_output(variable)?)
+    }
+}
+
+#[pymethods]
+impl PyRegistry {
+    #[new]
+    fn new() -> Self {
+        Self {
+            registry: ComponentRegistry::new(),
+            factory: ComponentFactory::with_builtins(),
+        }
+    }
+
+    /// Creates, initializes and adds a component
+    ///
+    /// `parameters` are applied before initialization, `initial` after.
+    #[pyo3(signature = (name, type_name, parameters = None, initial = None))]
+    fn add(
+        &mut self,
+        name: String,
+        type_name: &str,
+        parameters: Option<HashMap<String, f64>>,
+        initial: Option<HashMap<String, Value>>,
+    ) -> PyResult<()> {
+        let mut component = self.factory.create(type_name, &parameters.unwrap_or_default())?;
+        component.initialize()?;
+        for (variable, value) in initial.unwrap_or_default() {
+            set_value(component.as_mut(), &variable, value)?;
+        }
+        self.registry.add(name, component)?;
+        Ok(())
+    }
+
+    fn remove(&mut self, name: &str) -> PyResult<()> {
+        let id = self.registry.id_by_name(name)
+            .ok_or_else(|| PyKeyError::new_err(format!("Component '{}' not found", name)))?;
+        Ok(self.registry.remove(id)?)
+    }
+
+    /// Component names, sorted
+    fn names(&self) -> Vec<String> {
+        let mut names = self.registry.list_names();
+        names.sort();
+        names
+    }
+
+    fn __len__(&self) -> usize {
+        self.registry.len()
+    }
+
+    #[getter]
+    fn time(&self) -> f64 {
+        self.registry.time()
+    }
+
+    fn step_all(&mut self, dt: f64) -> PyResult<()> {
+        Ok(self.registry.step_all(dt)?)
+    }
+
+    /// Step timings per component name, durations in seconds
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for stats in &self.registry.stats().components {
+            dict.set_item(&stats.name, stats_to_dict(py, stats)?)?;
+        }
+        Ok(dict)
+    }
+
+    fn reset_stats(&mut self) {
+        self.registry.reset_stats();
+    }
+
+    fn get(&self, component: &str, variable: &str) -> PyResult<f64> {
+        Ok(self.component(component)?.get_output(variable)?)
+    }
+
+    fn set(&mut self, component: &str, variable: &str, value: Value) -> PyResult<()> {
+        set_value(self.component_mut(component)?.as_mut(), variable, value)
+    }
+
+    fn set_parameter(&mut self, component: &str, parameter: &str, value: f64) -> PyResult<()> {
+        Ok(self.component_mut(component)?.set_parameter(parameter, value)?)
+    }
+
+    /// Batch read of `"component.variable"` targets into one array
+    fn read<'py>(&self, py: Python<'py>, targets: Vec<String>) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        let values = targets.iter()
+            .map(|target| self.read_target(target))
+            .collect::<PyResult<Vec<f64>>>()?;
+        Ok(PyArray1::from_vec(py, values))
+    }
+
+    /// Steps until `stop_time` seconds from now, recording each
+    /// `"component.variable"` in `record` after every step
+    ///
+    /// Returns `{"time": array, target: array, ...}` including the
+    /// starting sample.
+    #[pyo3(signature = (stop_time, step_size, record = Vec::new()))]
+    fn run<'py>(
+        &mut self,
+        py: Python<'py>,
+        stop_time: f64,
+        step_size: f64,
+        record: Vec<String>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let steps = step_count(stop_time, step_size)?;
+
+        let mut time = Vec::with_capacity(steps + 1);
+        let mut columns: Vec<Vec<f64>> = vec![Vec::with_capacity(steps + 1); record.len()];
+
+        for step in 0..=steps {
+            if step > 0 {
+                self.step_all(step_size)?;
+            }
+            time.push(self.registry.time());
+            for (target, column) in record.iter().zip(&mut columns) {
+                column.push(self.read_target(target)?);
+            }
+        }
+
+        let mut result = vec![("time".to_string(), time)];
+        result.extend(record.into_iter().zip(columns));
+        columns_to_dict(py, result)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Registry({:?})", self.names())
+    }
+}
+
+/// Runs one simulation per value of `parameter` and returns `output` as a
+/// `(len(values), steps + 1)` array
+#[pyfunction]
+#[pyo3(signature = (type_name, parameter, values, output, stop_time, step_size, parameters = None, initial = None))]
+#[allow(clippy::too_many_arguments)]
+fn sweep<'py>(
+    py: Python<'py>,
+    type_name: &str,
+    parameter: &str,
+    values: Vec<f64>,
+    output: &str,
+    stop_time: f64,
+    step_size: f64,
+    parameters: Option<HashMap<String, f64>>,
+    initial: Option<HashMap<String, Value>>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let steps = step_count(stop_time, step_size)?;
+    let factory = ComponentFactory::with_builtins();
+    let mut parameters = parameters.unwrap_or_default();
+    let initial = initial.unwrap_or_default();
+
+    let mut rows = Vec::with_capacity(values.len());
+    for value in values {
+        parameters.insert(parameter.to_string(), value);
+        let mut component = factory.create(type_name, &parameters)?;
+        component.initialize()?;
+        for (variable, value) in &initial {
+            set_value(component.as_mut(), variable, *value)?;
+        }
+
+        let mut row = Vec::with_capacity(steps + 1);
+        row.push(component.get_output(output)?);
+        for _ in 0..steps {
+            component.step(step_size)?;
+            row.push(component.get_output(output)?);
+        }
+        rows.push(row);
+    }
+
+    PyArray2::from_vec2(py, &rows).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+#[pymodule]
+fn modelica_rust_ffi(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyModelicaRuntime>()?;
+    m.add_class::<PyComponent>()?;
+    m.add_class::<PyRegistry>()?;
+    m.add_function(wrap_pyfunction!(sweep, m)?)?;
+    m.add("SimulationError", m.py().get_type::<SimulationError>())?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
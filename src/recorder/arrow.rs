@@ -0,0 +1,279 @@
+use super::{Column, ResultSink};
+use crate::component::{ComponentError, ComponentResult};
+use arrow_array::{ArrayRef, Float64Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Rows per record batch unless configured
+pub const DEFAULT_BATCH_SIZE: usize = 8192;
+
+/// Arrow schema for recorded columns
+///
+/// Every column is a non-nullable `Float64` (booleans as 0/1). Field
+/// metadata carries `unit`, `description`, `causality` and `variability`
+/// where known.
+pub fn arrow_schema(columns: &[Column]) -> Schema {
+    let fields: Vec<Field> = columns.iter()
+        .map(|column| {
+            let mut metadata = HashMap::new();
+            if let Some(unit) = &column.unit {
+                metadata.insert("unit".to_string(), unit.clone());
+            }
+            if let Some(description) = &column.description {
+                metadata.insert("description".to_string(), description.clone());
+            }
+            metadata.insert("causality".to_string(), format!("{:?}", column.causality).to_lowercase());
+            metadata.insert("variability".to_string(), format!("{:?}", column.variability).to_lowercase());
+            Field::new(&column.name, DataType::Float64, false).with_metadata(metadata)
+        })
+        .collect();
+    Schema::new(fields)
+}
+
+/// Internal: Buffers rows column-wise until a batch is full
+struct BatchBuilder {
+    schema: SchemaRef,
+    columns: Vec<Vec<f64>>,
+    batch_size: usize,
+}
+
+impl BatchBuilder {
+    fn new(columns: &[Column], batch_size: usize) -> Self {
+        Self {
+            schema: Arc::new(arrow_schema(columns)),
+            columns: vec![Vec::with_capacity(batch_size); columns.len()],
+            batch_size,
+        }
+    }
+
+    /// Appends a row, returning a batch once `batch_size` rows are buffered
+    fn push(&mut self, values: &[f64]) -> ComponentResult<Option<RecordBatch>> {
+        if values.len() != self.columns.len() {
+            return Err(ComponentError::RecordingFailed(format!(
+                "Expected {} values per row, got {}", self.columns.len(), values.len()
+            )));
+        }
+        for (column, &value) in self.columns.iter_mut().zip(values) {
+            column.push(value);
+        }
+
+        if self.columns.first().map_or(0, Vec::len) >= self.batch_size {
+            self.take().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Builds a batch from the buffered rows (possibly empty)
+    fn take(&mut self) -> ComponentResult<RecordBatch> {
+        let arrays: Vec<ArrayRef> = self.columns.iter_mut()
+            .map(|column| {
+                let values = std::mem::replace(column, Vec::with_capacity(self.batch_size));
+                Arc::new(Float64Array::from(values)) as ArrayRef
+            })
+            .collect();
+        RecordBatch::try_new(self.schema.clone(), arrays)
+            .map_err(|e| ComponentError::RecordingFailed(format!("Failed to build record batch: {}", e)))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.columns.first().is_none_or(Vec::is_empty)
+    }
+}
+
+/// Collects rows into in-memory Arrow record batches
+///
+/// Each batch holds up to `batch_size` rows with one `Float64` column per
+/// recorded variable, time first. Hand the batches to DataFusion, Polars or
+/// an Arrow IPC writer after `finish`.
+pub struct ArrowSink {
+    batch_size: usize,
+    builder: Option<BatchBuilder>,
+    batches: Vec<RecordBatch>,
+}
+
+impl ArrowSink {
+    pub fn new() -> Self {
+        Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            builder: None,
+            batches: Vec::new(),
+        }
+    }
+
+    /// Rows per record batch
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Schema of the batches, available after `begin`
+    pub fn schema(&self) -> Option<SchemaRef> {
+        self.builder.as_ref().map(|builder| builder.schema.clone())
+    }
+
+    /// Completed batches; rows since the last full batch appear after `finish`
+    pub fn batches(&self) -> &[RecordBatch] {
+        &self.batches
+    }
+
+    pub fn into_batches(self) -> Vec<RecordBatch> {
+        self.batches
+    }
+}
+
+impl Default for ArrowSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResultSink for ArrowSink {
+    fn begin(&mut self, columns: &[Column]) -> ComponentResult<()> {
+        self.builder = Some(BatchBuilder::new(columns, self.batch_size));
+        Ok(())
+    }
+
+    fn write_row(&mut self, values: &[f64]) -> ComponentResult<()> {
+        let builder = self.builder.as_mut()
+            .ok_or_else(|| ComponentError::RecordingFailed("Sink not started".to_string()))?;
+        if let Some(batch) = builder.push(values)? {
+            self.batches.push(batch);
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> ComponentResult<()> {
+        if let Some(builder) = self.builder.as_mut().filter(|builder| !builder.is_empty()) {
+            let batch = builder.take()?;
+            self.batches.push(batch);
+        }
+        Ok(())
+    }
+}
+
+/// Internal: Where a `ParquetSink` is in its lifecycle
+enum ParquetState<W: Write + Send> {
+    Pending(W),
+    Writing(Box<ArrowWriter<W>>),
+    Closed(W),
+    Failed,
+}
+
+/// Streams rows to a Parquet file
+///
+/// The schema is that of `arrow_schema`; the Arrow schema with its field
+/// metadata is embedded in the file, so units and descriptions survive a
+/// round trip through pandas, Polars or DuckDB. Rows are written in batches
+/// of `batch_size` and compressed with Snappy.
+pub struct ParquetSink<W: Write + Send> {
+    state: ParquetState<W>,
+    batch_size: usize,
+    builder: Option<BatchBuilder>,
+}
+
+impl ParquetSink<BufWriter<File>> {
+    /// Creates (or truncates) a Parquet file
+    pub fn create<P: AsRef<Path>>(path: P) -> ComponentResult<Self> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|e| {
+            ComponentError::RecordingFailed(format!("Failed to create {}: {}", path.display(), e))
+        })?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write + Send> ParquetSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            state: ParquetState::Pending(writer),
+            batch_size: DEFAULT_BATCH_SIZE,
+            builder: None,
+        }
+    }
+
+    /// Rows buffered before they are written out
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Finishes the file if needed and returns the underlying writer
+    pub fn into_inner(mut self) -> ComponentResult<W> {
+        self.finish()?;
+        match self.state {
+            ParquetState::Closed(writer) | ParquetState::Pending(writer) => Ok(writer),
+            _ => Err(ComponentError::RecordingFailed("Parquet file was not completed".to_string())),
+        }
+    }
+
+    fn write_batch(&mut self, batch: &RecordBatch) -> ComponentResult<()> {
+        match &mut self.state {
+            ParquetState::Writing(writer) => writer.write(batch).map_err(parquet_error),
+            _ => Err(ComponentError::RecordingFailed("Sink not started".to_string())),
+        }
+    }
+}
+
+impl<W: Write + Send> ResultSink for ParquetSink<W> {
+    fn begin(&mut self, columns: &[Column]) -> ComponentResult<()> {
+        let writer = match std::mem::replace(&mut self.state, ParquetState::Failed) {
+            ParquetState::Pending(writer) => writer,
+            _ => return Err(ComponentError::RecordingFailed("Sink already started".to_string())),
+        };
+
+        let builder = BatchBuilder::new(columns, self.batch_size);
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let arrow = ArrowWriter::try_new(writer, builder.schema.clone(), Some(properties))
+            .map_err(parquet_error)?;
+
+        self.state = ParquetState::Writing(Box::new(arrow));
+        self.builder = Some(builder);
+        Ok(())
+    }
+
+    fn write_row(&mut self, values: &[f64]) -> ComponentResult<()> {
+        let builder = self.builder.as_mut()
+            .ok_or_else(|| ComponentError::RecordingFailed("Sink not started".to_string()))?;
+        if let Some(batch) = builder.push(values)? {
+            self.write_batch(&batch)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> ComponentResult<()> {
+        if let Some(builder) = self.builder.as_mut().filter(|builder| !builder.is_empty()) {
+            let batch = builder.take()?;
+            self.write_batch(&batch)?;
+        }
+
+        match std::mem::replace(&mut self.state, ParquetState::Failed) {
+            ParquetState::Writing(arrow) => {
+                let mut writer = arrow.into_inner().map_err(parquet_error)?;
+                writer.flush().map_err(|e| {
+                    ComponentError::RecordingFailed(format!("Failed to write Parquet: {}", e))
+                })?;
+                self.state = ParquetState::Closed(writer);
+                Ok(())
+            }
+            // Never started, or already finished
+            state => {
+                self.state = state;
+                Ok(())
+            }
+        }
+    }
+}
+
+fn parquet_error(e: parquet::errors::ParquetError) -> ComponentError {
+    ComponentError::RecordingFailed(format!("Failed to write Parquet: {}", e))
+}
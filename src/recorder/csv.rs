@@ -0,0 +1,77 @@
+use super::{Column, ResultSink};
+use crate::component::{ComponentError, ComponentResult};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Streams rows to CSV
+///
+/// The header holds one `name [unit]` cell per column (no brackets for
+/// unitless variables). Values use Rust's shortest round-trip formatting.
+pub struct CsvSink<W: Write> {
+    writer: W,
+}
+
+impl CsvSink<BufWriter<File>> {
+    /// Creates (or truncates) a CSV file
+    pub fn create<P: AsRef<Path>>(path: P) -> ComponentResult<Self> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|e| {
+            ComponentError::RecordingFailed(format!("Failed to create {}: {}", path.display(), e))
+        })?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write> CsvSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> ResultSink for CsvSink<W> {
+    fn begin(&mut self, columns: &[Column]) -> ComponentResult<()> {
+        let header: Vec<String> = columns.iter()
+            .map(|column| {
+                let cell = match &column.unit {
+                    Some(unit) => format!("{} [{}]", column.name, unit),
+                    None => column.name.clone(),
+                };
+                escape(&cell)
+            })
+            .collect();
+        writeln!(self.writer, "{}", header.join(",")).map_err(write_error)
+    }
+
+    fn write_row(&mut self, values: &[f64]) -> ComponentResult<()> {
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                self.writer.write_all(b",").map_err(write_error)?;
+            }
+            write!(self.writer, "{}", value).map_err(write_error)?;
+        }
+        self.writer.write_all(b"\n").map_err(write_error)
+    }
+
+    fn finish(&mut self) -> ComponentResult<()> {
+        self.writer.flush().map_err(write_error)
+    }
+}
+
+fn write_error(e: std::io::Error) -> ComponentError {
+    ComponentError::RecordingFailed(format!("Failed to write CSV: {}", e))
+}
+
+/// Quotes a cell containing separators or quotes
+fn escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
@@ -0,0 +1,207 @@
+use super::{Column, ResultSink};
+use crate::component::{Causality, ComponentError, ComponentResult, Variability};
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// MAT v4 type codes (little-endian, full matrix)
+const TYPE_DOUBLE: i32 = 0;
+const TYPE_INT32: i32 = 20;
+const TYPE_TEXT: i32 = 51;
+
+/// Streams rows to OpenModelica's MAT v4 result layout
+///
+/// The file holds the matrices of an OpenModelica `_res.mat`, so it opens
+/// in OMEdit, OMPython and DyMat:
+///
+/// * `Aclass` - `Atrajectory`, `1.1`, `binTrans`
+/// * `name`, `description` - one NUL-padded column per variable; units are
+///   appended to descriptions as ` [unit]`
+/// * `dataInfo` - the data matrix and column of each variable
+/// * `data_1` - time and fixed parameters at start and stop time
+/// * `data_2` - time and every other variable, one column per row
+///
+/// Fixed parameters are taken from the first row. The number of rows and
+/// the stop time are patched in `finish`, hence the `Seek` bound.
+pub struct MatSink<W: Write + Seek> {
+    writer: W,
+    /// Row positions stored in `data_1` and `data_2`, time first in both
+    data_1: Vec<usize>,
+    data_2: Vec<usize>,
+    stop_time_offset: u64,
+    row_count_offset: u64,
+    rows: i32,
+    last_time: f64,
+    column: Vec<u8>,
+}
+
+impl MatSink<BufWriter<File>> {
+    /// Creates (or truncates) a `.mat` file
+    pub fn create<P: AsRef<Path>>(path: P) -> ComponentResult<Self> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|e| {
+            ComponentError::RecordingFailed(format!("Failed to create {}: {}", path.display(), e))
+        })?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write + Seek> MatSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            data_1: Vec::new(),
+            data_2: Vec::new(),
+            stop_time_offset: 0,
+            row_count_offset: 0,
+            rows: 0,
+            last_time: 0.0,
+            column: Vec::new(),
+        }
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Internal: Writes `data_1` and the `data_2` header before the first row
+    fn write_data_1(&mut self, first_row: &[f64]) -> io::Result<()> {
+        write_header(&mut self.writer, TYPE_DOUBLE, self.data_1.len(), 2, "data_1")?;
+
+        // Column-major: start values, then stop values (time patched later)
+        let start = self.writer.stream_position()?;
+        self.stop_time_offset = start + 8 * self.data_1.len() as u64;
+        for _ in 0..2 {
+            for &index in &self.data_1 {
+                let value = first_row.get(index).copied().unwrap_or(0.0);
+                self.writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+
+        self.row_count_offset = self.writer.stream_position()? + 8;
+        write_header(&mut self.writer, TYPE_DOUBLE, self.data_2.len(), 0, "data_2")
+    }
+
+    fn patch(&mut self) -> io::Result<()> {
+        if self.rows == 0 {
+            self.write_data_1(&[])?;
+        }
+        let end = self.writer.stream_position()?;
+
+        self.writer.seek(SeekFrom::Start(self.row_count_offset))?;
+        self.writer.write_all(&self.rows.to_le_bytes())?;
+        self.writer.seek(SeekFrom::Start(self.stop_time_offset))?;
+        self.writer.write_all(&self.last_time.to_le_bytes())?;
+
+        self.writer.seek(SeekFrom::Start(end))?;
+        self.writer.flush()
+    }
+}
+
+impl<W: Write + Seek> ResultSink for MatSink<W> {
+    fn begin(&mut self, columns: &[Column]) -> ComponentResult<()> {
+        let mut data_info = Vec::with_capacity(columns.len() * 4);
+        for (index, column) in columns.iter().enumerate() {
+            let fixed = column.causality == Causality::Parameter
+                && matches!(column.variability, Variability::Fixed | Variability::Constant);
+
+            // [matrix, 1-based column, interpolation, extrapolation]
+            if index == 0 {
+                self.data_1.push(0);
+                self.data_2.push(0);
+                data_info.extend([0, 1, 0, -1]);
+            } else if fixed {
+                self.data_1.push(index);
+                data_info.extend([1, self.data_1.len() as i32, 0, 0]);
+            } else {
+                self.data_2.push(index);
+                data_info.extend([2, self.data_2.len() as i32, 0, -1]);
+            }
+        }
+
+        let names: Vec<String> = columns.iter().map(|c| c.name.clone()).collect();
+        let descriptions: Vec<String> = columns.iter()
+            .map(|c| {
+                let description = c.description.clone().unwrap_or_default();
+                match &c.unit {
+                    Some(unit) if description.is_empty() => format!("[{}]", unit),
+                    Some(unit) => format!("{} [{}]", description, unit),
+                    None => description,
+                }
+            })
+            .collect();
+
+        write_aclass(&mut self.writer)
+            .and_then(|_| write_strings(&mut self.writer, "name", &names))
+            .and_then(|_| write_strings(&mut self.writer, "description", &descriptions))
+            .and_then(|_| write_header(&mut self.writer, TYPE_INT32, 4, columns.len(), "dataInfo"))
+            .and_then(|_| {
+                let bytes: Vec<u8> = data_info.iter().flat_map(|v| v.to_le_bytes()).collect();
+                self.writer.write_all(&bytes)
+            })
+            .map_err(write_error)
+    }
+
+    fn write_row(&mut self, values: &[f64]) -> ComponentResult<()> {
+        if self.rows == 0 {
+            self.write_data_1(values).map_err(write_error)?;
+        }
+
+        self.column.clear();
+        for &index in &self.data_2 {
+            let value = values.get(index).copied().unwrap_or(f64::NAN);
+            self.column.extend_from_slice(&value.to_le_bytes());
+        }
+        self.writer.write_all(&self.column).map_err(write_error)?;
+
+        self.rows += 1;
+        self.last_time = values.first().copied().unwrap_or(0.0);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> ComponentResult<()> {
+        self.patch().map_err(write_error)
+    }
+}
+
+fn write_error(e: io::Error) -> ComponentError {
+    ComponentError::RecordingFailed(format!("Failed to write MAT file: {}", e))
+}
+
+/// Writes a matrix header: type, rows, columns, no imaginary part, name
+fn write_header<W: Write>(writer: &mut W, type_code: i32, rows: usize, cols: usize, name: &str) -> io::Result<()> {
+    for value in [type_code, rows as i32, cols as i32, 0, name.len() as i32 + 1] {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    writer.write_all(name.as_bytes())?;
+    writer.write_all(&[0])
+}
+
+/// Writes `Aclass`: four space-padded rows, stored column-major
+fn write_aclass<W: Write>(writer: &mut W) -> io::Result<()> {
+    const ROWS: [&str; 4] = ["Atrajectory", "1.1", "", "binTrans"];
+    const WIDTH: usize = 11;
+
+    write_header(writer, TYPE_TEXT, ROWS.len(), WIDTH, "Aclass")?;
+    let mut bytes = Vec::with_capacity(ROWS.len() * WIDTH);
+    for col in 0..WIDTH {
+        for row in ROWS {
+            bytes.push(row.as_bytes().get(col).copied().unwrap_or(b' '));
+        }
+    }
+    writer.write_all(&bytes)
+}
+
+/// Writes strings transposed: one NUL-padded column per string
+fn write_strings<W: Write>(writer: &mut W, name: &str, strings: &[String]) -> io::Result<()> {
+    let length = strings.iter().map(|s| s.len()).max().unwrap_or(0) + 1;
+
+    write_header(writer, TYPE_TEXT, length, strings.len(), name)?;
+    let mut bytes = Vec::with_capacity(length * strings.len());
+    for string in strings {
+        bytes.extend_from_slice(string.as_bytes());
+        bytes.resize(bytes.len() + length - string.len(), 0);
+    }
+    writer.write_all(&bytes)
+}
@@ -0,0 +1,342 @@
+//! Result recording
+//!
+//! A `Recorder` samples selected variables of a `ModelicaRuntime` or a
+//! `ComponentRegistry` and streams them to a `ResultSink` such as
+//! `CsvSink`, `MatSink` or, with the `arrow` feature, `ParquetSink`.
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod csv;
+pub mod mat;
+
+#[cfg(feature = "arrow")]
+pub use self::arrow::{ArrowSink, ParquetSink};
+pub use self::csv::CsvSink;
+pub use self::mat::MatSink;
+
+use crate::component::{Causality, ComponentError, ComponentResult, IOSpec, IOType, Variability};
+use crate::registry::ComponentRegistry;
+use crate::runtime::ModelicaRuntime;
+
+/// Something a `Recorder` can sample
+pub trait RecordSource {
+    /// Every recordable variable under the name used to select and read it
+    fn recordable_variables(&self) -> Vec<IOSpec>;
+
+    /// Current value of a variable (booleans as 0/1)
+    fn read_variable(&self, name: &str) -> ComponentResult<f64>;
+}
+
+impl RecordSource for ModelicaRuntime {
+    fn recordable_variables(&self) -> Vec<IOSpec> {
+        self.variables().iter()
+            .filter(|spec| spec.causality != Causality::Independent)
+            .cloned()
+            .collect()
+    }
+
+    fn read_variable(&self, name: &str) -> ComponentResult<f64> {
+        match self.variable(name).map(|spec| spec.io_type) {
+            Some(IOType::Boolean) => self.get_bool_variable(name).map(|b| if b { 1.0 } else { 0.0 }),
+            _ => self.get_real_variable(name),
+        }
+    }
+}
+
+/// Variables are named `"component.variable"`, components sorted by name
+impl RecordSource for ComponentRegistry {
+    fn recordable_variables(&self) -> Vec<IOSpec> {
+        let mut names = self.list_names();
+        names.sort();
+
+        names.iter()
+            .filter_map(|component| self.get_by_name(component).map(|c| (component, c.metadata())))
+            .flat_map(|(component, metadata)| {
+                metadata.variables()
+                    .map(|spec| IOSpec {
+                        name: format!("{}.{}", component, spec.name),
+                        ..spec.clone()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn read_variable(&self, name: &str) -> ComponentResult<f64> {
+        let (component, variable) = name.split_once('.')
+            .ok_or_else(|| ComponentError::VariableNotFound(name.to_string()))?;
+        self.get_by_name(component)
+            .ok_or_else(|| ComponentError::VariableNotFound(name.to_string()))?
+            .get_output(variable)
+    }
+}
+
+/// One recorded column
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub unit: Option<String>,
+    pub description: Option<String>,
+    pub causality: Causality,
+    pub variability: Variability,
+}
+
+impl Column {
+    /// The time column every recording starts with
+    pub fn time() -> Self {
+        Self {
+            name: "time".to_string(),
+            unit: Some("s".to_string()),
+            description: Some("Simulation time".to_string()),
+            causality: Causality::Independent,
+            variability: Variability::Continuous,
+        }
+    }
+
+    fn from_spec(spec: &IOSpec) -> Self {
+        Self {
+            name: spec.name.clone(),
+            unit: spec.unit.clone(),
+            description: spec.description.clone(),
+            causality: spec.causality,
+            variability: spec.variability,
+        }
+    }
+}
+
+/// Destination for recorded rows
+///
+/// `begin` is called once with the columns (time first), then `write_row`
+/// once per sample with one value per column, then `finish`.
+pub trait ResultSink {
+    fn begin(&mut self, columns: &[Column]) -> ComponentResult<()>;
+
+    fn write_row(&mut self, values: &[f64]) -> ComponentResult<()>;
+
+    /// Flush and close; no rows are written afterwards
+    fn finish(&mut self) -> ComponentResult<()>;
+}
+
+impl<S: ResultSink + ?Sized> ResultSink for Box<S> {
+    fn begin(&mut self, columns: &[Column]) -> ComponentResult<()> {
+        (**self).begin(columns)
+    }
+
+    fn write_row(&mut self, values: &[f64]) -> ComponentResult<()> {
+        (**self).write_row(values)
+    }
+
+    fn finish(&mut self) -> ComponentResult<()> {
+        (**self).finish()
+    }
+}
+
+/// Samples selected variables into a `ResultSink`
+///
+/// Variables are selected with glob patterns (`*` matches any run of
+/// characters, `?` a single one) against the source's variable names; with
+/// no patterns every output is recorded. `sample` records at most once per
+/// output interval, `record` unconditionally (e.g. on events).
+///
+/// # Examples
+///
+/// ```no_run
+/// use modelica_rust_ffi::recorder::{CsvSink, Recorder};
+/// use modelica_rust_ffi::Scenario;
+///
+/// let scenario = Scenario::from_file("colony.toml")?;
+/// let mut registry = scenario.build_registry()?;
+///
+/// let mut recorder = Recorder::new(CsvSink::create("results.csv")?)
+///     .select("habitat_*.temperature")
+///     .with_interval(1.0);
+/// recorder.start(&registry)?;
+///
+/// scenario.run_with(&mut registry, |time, registry| recorder.sample(time, registry))?;
+/// recorder.finish()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Recorder<S: ResultSink> {
+    sink: S,
+    patterns: Vec<String>,
+    interval: Option<f64>,
+    columns: Vec<String>,
+    next_sample: Option<f64>,
+    row: Vec<f64>,
+    started: bool,
+    finished: bool,
+}
+
+impl<S: ResultSink> Recorder<S> {
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            patterns: Vec::new(),
+            interval: None,
+            columns: Vec::new(),
+            next_sample: None,
+            row: Vec::new(),
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Adds a glob pattern selecting variables to record
+    pub fn select(mut self, pattern: &str) -> Self {
+        self.patterns.push(pattern.to_string());
+        self
+    }
+
+    /// Records at most once every `interval` seconds in `sample`
+    pub fn with_interval(mut self, interval: f64) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Names of the recorded variables, available after `start`
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Resolves the selection against `source` and writes the header
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::VariableNotFound` if a pattern matches no
+    /// variable, and `ComponentError::InvalidInput` for a non-positive
+    /// interval.
+    pub fn start<R: RecordSource + ?Sized>(&mut self, source: &R) -> ComponentResult<()> {
+        if self.started {
+            return Err(ComponentError::RecordingFailed("Recorder already started".to_string()));
+        }
+        if let Some(interval) = self.interval {
+            if interval <= 0.0 || !interval.is_finite() {
+                return Err(ComponentError::InvalidInput(
+                    format!("Invalid output interval: {}. Must be positive and finite.", interval)
+                ));
+            }
+        }
+
+        let variables = source.recordable_variables();
+        let mut selected: Vec<&IOSpec> = Vec::new();
+
+        if self.patterns.is_empty() {
+            selected.extend(variables.iter().filter(|spec| spec.causality == Causality::Output));
+        }
+        for pattern in &self.patterns {
+            let matches: Vec<&IOSpec> = variables.iter()
+                .filter(|spec| glob_match(pattern, &spec.name))
+                .collect();
+            if matches.is_empty() {
+                return Err(ComponentError::VariableNotFound(pattern.clone()));
+            }
+            for spec in matches {
+                if !selected.iter().any(|s| s.name == spec.name) {
+                    selected.push(spec);
+                }
+            }
+        }
+
+        let mut columns = vec![Column::time()];
+        columns.extend(selected.iter().map(|spec| Column::from_spec(spec)));
+        self.sink.begin(&columns)?;
+
+        self.columns = selected.iter().map(|spec| spec.name.clone()).collect();
+        self.row = Vec::with_capacity(columns.len());
+        self.started = true;
+        Ok(())
+    }
+
+    /// Records a row if `time` has reached the next output time
+    ///
+    /// Without an interval every call records. Values are those at `time`;
+    /// nothing is interpolated, so pick an interval that is a multiple of
+    /// the step size for evenly spaced rows.
+    pub fn sample<R: RecordSource + ?Sized>(&mut self, time: f64, source: &R) -> ComponentResult<()> {
+        let interval = match (self.interval, self.next_sample) {
+            (None, _) => return self.record(time, source),
+            (Some(interval), None) => interval,
+            (Some(interval), Some(next)) => {
+                // Tolerance for accumulated rounding in the caller's time
+                if time < next - interval * 1e-9 {
+                    return Ok(());
+                }
+                interval
+            }
+        };
+
+        self.record(time, source)?;
+
+        let mut next = self.next_sample.unwrap_or(time) + interval;
+        while next <= time + interval * 1e-9 {
+            next += interval;
+        }
+        self.next_sample = Some(next);
+        Ok(())
+    }
+
+    /// Records a row now, regardless of the output interval
+    pub fn record<R: RecordSource + ?Sized>(&mut self, time: f64, source: &R) -> ComponentResult<()> {
+        if !self.started {
+            self.start(source)?;
+        }
+        if self.finished {
+            return Err(ComponentError::RecordingFailed("Recorder already finished".to_string()));
+        }
+
+        self.row.clear();
+        self.row.push(time);
+        for name in &self.columns {
+            self.row.push(source.read_variable(name)?);
+        }
+        self.sink.write_row(&self.row)
+    }
+
+    /// Flushes the sink; further samples are rejected
+    pub fn finish(&mut self) -> ComponentResult<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+        self.sink.finish()
+    }
+
+    /// Finishes and returns the sink
+    pub fn into_sink(mut self) -> ComponentResult<S> {
+        self.finish()?;
+        Ok(self.sink)
+    }
+}
+
+/// Matches `name` against a glob with `*` and `?` wildcards
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*` and the name position it is matching from
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_n)) => {
+                    p = star_p;
+                    n = star_n + 1;
+                    backtrack = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
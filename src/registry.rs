@@ -1,10 +1,19 @@
 use crate::component::{SimulationComponent, ComponentResult, ComponentError};
+use crate::observer::{self, Observable, Observers};
+use crate::stats::{self, RegistryStats, StepStats, StepTimer, Stopwatch};
 use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
 pub struct ComponentRegistry {
     components: HashMap<Uuid, Box<dyn SimulationComponent>>,
     name_to_id: HashMap<String, Uuid>,
+    time: f64,
+    observers: Observers<ComponentRegistry>,
+    /// One per component, also holding its name
+    timers: HashMap<Uuid, StepTimer>,
+    steps: u64,
+    step_time: Duration,
 }
 
 impl ComponentRegistry {
@@ -12,6 +21,11 @@ impl ComponentRegistry {
         Self {
             components: HashMap::new(),
             name_to_id: HashMap::new(),
+            time: 0.0,
+            observers: Observers::new(),
+            timers: HashMap::new(),
+            steps: 0,
+            step_time: Duration::ZERO,
         }
     }
     
@@ -24,6 +38,7 @@ impl ComponentRegistry {
         }
         
         self.components.insert(id, component);
+        self.timers.insert(id, StepTimer::new(name.clone()));
         self.name_to_id.insert(name, id);
         Ok(())
     }
@@ -42,6 +57,7 @@ impl ComponentRegistry {
         
         // Remove from name map
         self.name_to_id.retain(|_, v| *v != id);
+        self.timers.remove(&id);
         Ok(())
     }
     
@@ -66,21 +82,134 @@ impl ComponentRegistry {
             .and_then(|id| self.get(*id))
     }
     
+    /// Look up a component's ID by name
+    pub fn id_by_name(&self, name: &str) -> Option<Uuid> {
+        self.name_to_id.get(name).copied()
+    }
+    
     /// Step all components
+    ///
+    /// Registered observers are notified before and after the step, and
+    /// with the failing component's name if one fails. Each component's
+    /// step is timed for `stats`.
     pub fn step_all(&mut self, dt: f64) -> ComponentResult<()> {
-        for component in self.components.values_mut() {
-            component.step(dt)?;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("step_all", time = self.time, dt).entered();
+
+        let started = Stopwatch::start();
+        let result = observer::observe_step(self, self.time, dt, |registry| {
+            for (id, component) in registry.components.iter_mut() {
+                let timer = registry.timers.get_mut(id).expect("every component has a timer");
+                #[cfg(feature = "tracing")]
+                let _span = tracing::debug_span!("step", component = timer.name()).entered();
+
+                let solver_calls = component.solver_calls();
+                let allocations = stats::thread_allocations();
+                let step_started = Stopwatch::start();
+                let result = component.step(dt);
+                timer.record(
+                    step_started.elapsed(),
+                    component.solver_calls().zip(solver_calls).map(|(after, before)| after - before),
+                    stats::thread_allocations().saturating_sub(allocations),
+                );
+
+                if let Err(error) = result {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!(component = timer.name(), time = registry.time, %error, "step failed");
+                    return Err((Some(timer.name().to_string()), error));
+                }
+            }
+            registry.time += dt;
+            Ok(())
+        });
+
+        self.steps += 1;
+        self.step_time += started.elapsed();
+        result
+    }
+    
+    /// Initialize all components and restart the clock at 0
+    pub fn initialize_all(&mut self) -> ComponentResult<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("initialize_all").entered();
+
+        #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+        for (id, component) in self.components.iter_mut() {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("initialize", component = self.timers[id].name()).entered();
+            component.initialize()?;
+        }
+        self.time = 0.0;
+        Ok(())
+    }
+    
+    /// Reset all components to their initial state and the clock to 0
+    pub fn reset_all(&mut self) -> ComponentResult<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("reset_all", time = self.time).entered();
+
+        #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+        for (id, component) in self.components.iter_mut() {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("reset", component = self.timers[id].name()).entered();
+            component.reset()?;
         }
+        self.time = 0.0;
         Ok(())
     }
     
+    /// Simulated time: the sum of the `dt`s of successful `step_all` calls
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+    
+    /// Step timings per component since creation or `reset_stats`
+    pub fn stats(&self) -> RegistryStats {
+        let mut components: Vec<StepStats> = self.timers.values().map(StepTimer::snapshot).collect();
+        components.sort_by(|a, b| a.name.cmp(&b.name));
+        RegistryStats {
+            steps: self.steps,
+            total: self.step_time,
+            components,
+        }
+    }
+    
+    /// Clears the statistics returned by `stats`
+    pub fn reset_stats(&mut self) {
+        self.steps = 0;
+        self.step_time = Duration::ZERO;
+        self.timers.values_mut().for_each(StepTimer::reset);
+    }
+    
     /// List all component IDs
     pub fn list_ids(&self) -> Vec<Uuid> {
         self.components.keys().copied().collect()
     }
     
+    /// Number of components
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+    
+    /// Returns true if the registry holds no components
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+    
     /// List all component names
     pub fn list_names(&self) -> Vec<String> {
         self.name_to_id.keys().cloned().collect()
     }
+}
+
+impl Observable for ComponentRegistry {
+    fn observers(&mut self) -> &mut Observers<Self> {
+        &mut self.observers
+    }
+}
+
+impl Default for ComponentRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,8 @@
+//! Raw OpenModelica bindings, one module per compiled model
+//!
+//! The modules and `COMPILED_MODELS` are generated by `build.rs` from the
+//! models found in `space-colony-modelica-core/build/`.
+#![allow(dead_code)]
+#![allow(clippy::all)]
+
+include!(concat!(env!("OUT_DIR"), "/components_index.rs"));
@@ -0,0 +1,95 @@
+//! Guarded calls into OpenModelica-generated model code
+//!
+//! OpenModelica signals failed `assert()`s and solver errors by `longjmp`
+//! through `threadData_t`, which must never unwind across Rust frames.
+//! `mrf_guarded_call` in `mmc_guard.c` catches the jump in C and returns a
+//! status code; this module turns that into `ComponentError::RuntimeError`
+//! carrying the assertion message captured by the message hook.
+//!
+//! Scaffolding: `ModelicaRuntime` doesn't own a `DATA` / `threadData_t`
+//! yet and still steps the pure-Rust models, so only the tests below call
+//! into the guard. Every call into generated model code must go through
+//! `call` once it does.
+
+use super::messages::{self, RuntimeMessage};
+use crate::component::{ComponentError, ComponentResult};
+use std::os::raw::{c_int, c_void};
+
+/// A model function, `int f(DATA *data, threadData_t *threadData)`
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) type ModelFn = unsafe extern "C" fn(data: *mut c_void, thread_data: *mut c_void) -> c_int;
+
+const CALL_OK: c_int = 0;
+const CALL_FAILED: c_int = 1;
+
+extern "C" {
+    fn mrf_guarded_call(thread_data: *mut c_void, f: ModelFn, data: *mut c_void) -> c_int;
+}
+
+/// Calls `f(data, thread_data)` for `component` at `time`
+///
+/// `call` names the function in errors (e.g. `"functionODE"`). Returns the
+/// messages reported during the call with the result. After a failure the
+/// jump buffers are restored and the instance can be stepped again once
+/// the caller has rolled back its state.
+///
+/// # Safety
+///
+/// `data` and `thread_data` must be the `DATA` / `threadData_t` pair `f`
+/// was generated for, and `f` must not call back into Rust.
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) unsafe fn call(
+    component: &str,
+    time: f64,
+    call: &str,
+    f: ModelFn,
+    data: *mut c_void,
+    thread_data: *mut c_void,
+) -> (ComponentResult<()>, Vec<RuntimeMessage>) {
+    // SAFETY: upheld by the caller; the shim keeps longjmp() out of Rust
+    let (status, messages) = messages::scope(component, time, || unsafe { mrf_guarded_call(thread_data, f, data) });
+    let result = match status {
+        CALL_OK => Ok(()),
+        CALL_FAILED => Err(ComponentError::RuntimeError(format!("{} failed at t = {} s", call, time))),
+        _ => Err(messages::abort_error(&messages, call)),
+    };
+    (result, messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::Severity;
+
+    extern "C" {
+        // Stand-ins for generated model functions, in `mmc_guard_test.c`
+        fn mrf_test_thread_data() -> *mut c_void;
+        fn mrf_test_ok(data: *mut c_void, thread_data: *mut c_void) -> c_int;
+        fn mrf_test_fail(data: *mut c_void, thread_data: *mut c_void) -> c_int;
+        fn mrf_test_assert(data: *mut c_void, thread_data: *mut c_void) -> c_int;
+    }
+
+    /// Calls `f` guarded on the stub thread data
+    fn call_stub(f: ModelFn) -> (ComponentResult<()>, Vec<RuntimeMessage>) {
+        // SAFETY: the stubs ignore `data` and only jump through the thread data
+        unsafe { call("Stub", 2.5, "functionODE", f, std::ptr::null_mut(), mrf_test_thread_data()) }
+    }
+
+    #[test]
+    fn test_longjmp_surfaces_as_runtime_error() {
+        messages::install();
+
+        let (result, messages) = call_stub(mrf_test_assert);
+        assert!(matches!(&result, Err(ComponentError::RuntimeError(text)) if text == "stub assertion failed"));
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].severity, Severity::Assert);
+        assert_eq!(messages[0].component.as_deref(), Some("Stub"));
+        assert_eq!(messages[0].time, Some(2.5));
+
+        // The jump buffers were restored, so the guard keeps working
+        assert!(call_stub(mrf_test_ok).0.is_ok());
+        let (result, _) = call_stub(mrf_test_fail);
+        assert!(matches!(&result, Err(ComponentError::RuntimeError(text)) if text == "functionODE failed at t = 2.5 s"));
+        assert!(matches!(call_stub(mrf_test_assert).0, Err(ComponentError::RuntimeError(_))));
+    }
+}
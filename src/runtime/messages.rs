@@ -0,0 +1,267 @@
+//! OpenModelica runtime messages
+//!
+//! The OpenModelica runtime prints warnings, errors and assertion failures
+//! to stdout through its `messageFunction` hook. With the `openmodelica`
+//! feature `ModelicaRuntime` replaces that hook, so messages are attributed
+//! to the component and simulation time of the call that produced them and
+//! are reported as `tracing` events (target `modelica_rust_ffi::openmodelica`)
+//! with the `tracing` feature, or on stderr without it. Stdout stays free
+//! for results, e.g. `modelica-sim run` writing CSV.
+
+use crate::component::ComponentError;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::os::raw::{c_char, c_int};
+
+/// Severity of a runtime message, from OpenModelica's `LOG_TYPE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Debug,
+    Info,
+    Warning,
+    Error,
+    /// A failed Modelica `assert`
+    Assert,
+}
+
+impl Severity {
+    /// Maps OpenModelica's `LOG_TYPE` (`LOG_TYPE_INFO` = 1 ... `LOG_TYPE_DEBUG` = 5)
+    fn from_log_type(log_type: c_int) -> Self {
+        match log_type {
+            2 => Severity::Warning,
+            3 => Severity::Error,
+            4 => Severity::Assert,
+            5 => Severity::Debug,
+            _ => Severity::Info,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Debug => "debug",
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Assert => "assert",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A message from the model runtime
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeMessage {
+    /// Model name of the runtime that was being called, if any
+    pub component: Option<String>,
+    /// Simulation time at the start of that call
+    pub time: Option<f64>,
+    pub severity: Severity,
+    pub text: String,
+}
+
+impl fmt::Display for RuntimeMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.component, self.time) {
+            (Some(component), Some(time)) => write!(f, "{} at t = {} s: ", component, time)?,
+            (Some(component), None) => write!(f, "{}: ", component)?,
+            _ => {}
+        }
+        write!(f, "{}: {}", self.severity, self.text)
+    }
+}
+
+thread_local! {
+    /// Component name and time of the runtime call in progress
+    static CONTEXT: Cell<Option<(*const str, f64)>> = const { Cell::new(None) };
+    /// Messages reported during that call
+    static CAPTURED: RefCell<Vec<RuntimeMessage>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Internal: Runs `f` with messages attributed to `component` at `time`
+///
+/// Returns the messages reported while `f` ran, along with its result.
+pub(crate) fn scope<R>(component: &str, time: f64, f: impl FnOnce() -> R) -> (R, Vec<RuntimeMessage>) {
+    /// Restores the enclosing scope, also when `f` panics
+    struct Restore {
+        context: Option<(*const str, f64)>,
+        captured: Vec<RuntimeMessage>,
+    }
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CONTEXT.with(|context| context.set(self.context));
+            CAPTURED.with(|captured| *captured.borrow_mut() = std::mem::take(&mut self.captured));
+        }
+    }
+
+    let _restore = Restore {
+        context: CONTEXT.with(|context| context.replace(Some((component as *const str, time)))),
+        captured: CAPTURED.with(|captured| captured.take()),
+    };
+    let result = f();
+    (result, CAPTURED.with(|captured| captured.take()))
+}
+
+/// Reports a message from model code
+///
+/// The OpenModelica message hook goes through here; Rust model
+/// implementations can use it for the same kind of diagnostics.
+pub fn emit(severity: Severity, text: &str) {
+    let (component, time) = match CONTEXT.with(Cell::get) {
+        // SAFETY: `scope` keeps the name borrowed until it resets the context
+        Some((component, time)) => (Some(unsafe { &*component }), Some(time)),
+        None => (None, None),
+    };
+
+    #[cfg(feature = "tracing")]
+    {
+        macro_rules! event {
+            ($level:expr) => {
+                tracing::event!(
+                    target: "modelica_rust_ffi::openmodelica",
+                    $level,
+                    component = component,
+                    time = time,
+                    severity = %severity,
+                    "{}",
+                    text
+                )
+            };
+        }
+        match severity {
+            Severity::Debug => event!(tracing::Level::DEBUG),
+            Severity::Info => event!(tracing::Level::INFO),
+            Severity::Warning => event!(tracing::Level::WARN),
+            Severity::Error | Severity::Assert => event!(tracing::Level::ERROR),
+        }
+    }
+
+    let message = RuntimeMessage {
+        component: component.map(str::to_string),
+        time,
+        severity,
+        text: text.to_string(),
+    };
+    #[cfg(not(feature = "tracing"))]
+    if severity >= Severity::Warning {
+        eprintln!("{}", message);
+    }
+    if component.is_some() {
+        CAPTURED.with(|captured| captured.borrow_mut().push(message));
+    }
+}
+
+/// Internal: The error for a model call that the runtime aborted
+///
+/// Carries the last failed assertion reported during the call, else the
+/// last error, else names the call.
+#[cfg_attr(not(feature = "openmodelica"), allow(dead_code))]
+pub(crate) fn abort_error(messages: &[RuntimeMessage], call: &str) -> ComponentError {
+    let reported = messages.iter().rev()
+        .find(|m| m.severity == Severity::Assert)
+        .or_else(|| messages.iter().rev().find(|m| m.severity == Severity::Error));
+    match reported {
+        Some(message) => ComponentError::RuntimeError(message.text.clone()),
+        None => ComponentError::RuntimeError(format!("{} aborted without a message", call)),
+    }
+}
+
+/// Internal: Receives OpenModelica's runtime messages
+///
+/// Called by the `messageFunction` trampoline in `mmc_messages.c` with the
+/// hook's arguments except the by-value `FILE_INFO`.
+#[cfg_attr(not(feature = "openmodelica"), allow(dead_code))]
+pub(crate) unsafe extern "C" fn message_callback(
+    log_type: c_int,
+    _stream: c_int,
+    _indent_next: c_int,
+    msg: *mut c_char,
+    _subline: c_int,
+    _indexes: *const c_int,
+) {
+    if msg.is_null() {
+        return;
+    }
+    // SAFETY: OpenModelica passes a NUL-terminated message
+    let text = unsafe { std::ffi::CStr::from_ptr(msg) }.to_string_lossy();
+    // Never unwind into C
+    let _ = std::panic::catch_unwind(|| emit(Severity::from_log_type(log_type), text.trim_end()));
+}
+
+#[cfg(feature = "openmodelica")]
+mod hook {
+    use std::os::raw::{c_char, c_int};
+
+    type MessageCallback = unsafe extern "C" fn(c_int, c_int, c_int, *mut c_char, c_int, *const c_int);
+
+    extern "C" {
+        /// `mmc_messages.c`; points `messageFunction` at a trampoline to `callback`
+        fn mrf_install_message_hook(callback: MessageCallback);
+    }
+
+    /// Routes OpenModelica runtime messages through `emit`
+    pub(crate) fn install() {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+        INSTALL.call_once(|| {
+            // SAFETY: called once, before any model code runs
+            unsafe { mrf_install_message_hook(super::message_callback) };
+        });
+    }
+}
+
+#[cfg(feature = "openmodelica")]
+pub(crate) use hook::install;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    #[test]
+    fn test_scope_captures_callback_messages() {
+        let text = CString::new("Variable violating min constraint: 0.0 <= temperature\n").unwrap();
+        let ((), messages) = scope("SimpleThermalMVP", 12.5, || {
+            // SAFETY: valid NUL-terminated message
+            unsafe { message_callback(2, 0, 0, text.as_ptr() as *mut c_char, 0, std::ptr::null()) };
+            let ((), inner) = scope("Inner", 0.0, || emit(Severity::Info, "nested"));
+            assert_eq!(inner.len(), 1);
+        });
+
+        assert_eq!(messages, [RuntimeMessage {
+            component: Some("SimpleThermalMVP".to_string()),
+            time: Some(12.5),
+            severity: Severity::Warning,
+            text: "Variable violating min constraint: 0.0 <= temperature".to_string(),
+        }]);
+        assert_eq!(
+            messages[0].to_string(),
+            "SimpleThermalMVP at t = 12.5 s: warning: Variable violating min constraint: 0.0 <= temperature"
+        );
+        assert_eq!(CONTEXT.with(Cell::get), None);
+    }
+
+    #[test]
+    fn test_abort_error_prefers_assertions() {
+        let message = |severity, text: &str| RuntimeMessage {
+            component: Some("SimpleThermalMVP".to_string()),
+            time: Some(1.0),
+            severity,
+            text: text.to_string(),
+        };
+        let messages = [
+            message(Severity::Assert, "Model error: roomCapacity > 0 failed"),
+            message(Severity::Error, "Simulation terminated"),
+            message(Severity::Warning, "Step size reduced"),
+        ];
+
+        let error = abort_error(&messages, "functionODE");
+        assert!(matches!(&error, ComponentError::RuntimeError(text) if text == "Model error: roomCapacity > 0 failed"));
+        let error = abort_error(&messages[1..], "functionODE");
+        assert!(matches!(&error, ComponentError::RuntimeError(text) if text == "Simulation terminated"));
+        assert_eq!(
+            abort_error(&messages[2..], "functionODE").to_string(),
+            "OpenModelica runtime error: functionODE aborted without a message"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+/*
+ * Jump-buffer guard around calls into OpenModelica-generated model code.
+ *
+ * The OpenModelica runtime reports failed assert()s, solver errors and
+ * throw()s by longjmp()ing to one of the jump buffers in threadData_t. If
+ * no buffer is set up, or the jump crosses Rust frames, the behaviour is
+ * undefined. mrf_guarded_call() points every buffer at its own setjmp(),
+ * so a failure returns here instead; the buffers and the error stage are
+ * restored in both cases. The message itself reaches Rust through the
+ * messageFunction hook (mmc_messages.c).
+ *
+ * Compiled by build.rs with the `openmodelica` feature.
+ */
+
+#include <setjmp.h>
+
+#include "openmodelica.h"
+#include "util/omc_error.h"
+
+#define MRF_CALL_OK 0
+#define MRF_CALL_FAILED 1
+#define MRF_CALL_ABORTED 2
+
+/* Model functions take the DATA and thread data and return non-zero on failure */
+typedef int (*mrf_model_fn)(void *data, threadData_t *threadData);
+
+/*
+ * Calls fn(data, threadData) with every OpenModelica jump buffer set.
+ *
+ * Returns MRF_CALL_OK on success, MRF_CALL_FAILED if fn returned non-zero
+ * and MRF_CALL_ABORTED if the runtime longjmp()ed out of it.
+ *
+ * fn must not call back into Rust: the longjmp() would skip its frames.
+ */
+int mrf_guarded_call(threadData_t *threadData, mrf_model_fn fn, void *data)
+{
+  jmp_buf buffer;
+  /* volatile: read after longjmp() */
+  jmp_buf *volatile old_mmc = threadData->mmc_jumper;
+  jmp_buf *volatile old_global = threadData->globalJumpBuffer;
+  jmp_buf *volatile old_simulation = threadData->simulationJumpBuffer;
+  volatile errorStage old_stage = threadData->currentErrorStage;
+  volatile int status = MRF_CALL_ABORTED;
+
+  threadData->mmc_jumper = &buffer;
+  threadData->globalJumpBuffer = &buffer;
+  threadData->simulationJumpBuffer = &buffer;
+  threadData->currentErrorStage = ERROR_SIMULATION;
+
+  if (setjmp(buffer) == 0) {
+    status = fn(data, threadData) == 0 ? MRF_CALL_OK : MRF_CALL_FAILED;
+  }
+
+  threadData->mmc_jumper = old_mmc;
+  threadData->globalJumpBuffer = old_global;
+  threadData->simulationJumpBuffer = old_simulation;
+  threadData->currentErrorStage = old_stage;
+  return status;
+}
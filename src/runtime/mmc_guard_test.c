@@ -0,0 +1,41 @@
+/*
+ * Stand-ins for OpenModelica-generated model functions, called by the unit
+ * tests in src/runtime/guard.rs.
+ *
+ * Compiled by build.rs with the `openmodelica` feature.
+ */
+
+#include <setjmp.h>
+#include <stddef.h>
+
+#include "openmodelica.h"
+#include "util/omc_error.h"
+
+static threadData_t mrf_test_thread;
+
+threadData_t *mrf_test_thread_data(void)
+{
+  return &mrf_test_thread;
+}
+
+int mrf_test_ok(void *data, threadData_t *threadData)
+{
+  (void)data;
+  (void)threadData;
+  return 0;
+}
+
+int mrf_test_fail(void *data, threadData_t *threadData)
+{
+  (void)data;
+  (void)threadData;
+  return 1;
+}
+
+/* Reports a failed assert() and jumps out, as generated code does */
+int mrf_test_assert(void *data, threadData_t *threadData)
+{
+  (void)data;
+  messageFunction(LOG_TYPE_ASSERT, LOG_ASSERT, omc_dummyFileInfo, 0, (char *)"stub assertion failed", 0, NULL);
+  longjmp(*threadData->mmc_jumper, 1);
+}
@@ -0,0 +1,38 @@
+/*
+ * Forwards OpenModelica runtime messages to Rust.
+ *
+ * omc_error.h declares the messageFunction hook with a FILE_INFO passed by
+ * value, whose layout belongs to the runtime. Rather than mirror it in Rust,
+ * the trampoline below is compiled against the real header and calls the
+ * Rust callback without it (see src/runtime/messages.rs).
+ *
+ * Compiled by build.rs with the `openmodelica` feature.
+ */
+
+#include <stddef.h>
+
+#include "openmodelica.h"
+#include "util/omc_error.h"
+
+/* messageFunction without the FILE_INFO */
+typedef void (*mrf_message_fn)(int type, int stream, int indentNext, char *msg, int subline, const int *indexes);
+
+static mrf_message_fn mrf_message_callback = NULL;
+
+static void mrf_message_trampoline(int type, int stream, FILE_INFO info, int indentNext, char *msg,
+                                   int subline, const int *indexes)
+{
+  (void)info;
+  mrf_message_callback(type, stream, indentNext, msg, subline, indexes);
+}
+
+/*
+ * Routes every runtime message to callback.
+ *
+ * Call once, before any model code runs.
+ */
+void mrf_install_message_hook(mrf_message_fn callback)
+{
+  mrf_message_callback = callback;
+  messageFunction = mrf_message_trampoline;
+}
@@ -1,3 +1,10 @@
+#[cfg(feature = "openmodelica")]
+pub mod bindings;
+#[cfg(feature = "openmodelica")]
+pub(crate) mod guard;
+pub mod messages;
 pub mod modelica_runtime;
+mod native;
 
-pub use modelica_runtime::ModelicaRuntime;
\ No newline at end of file
+pub use messages::{RuntimeMessage, Severity};
+pub use modelica_runtime::ModelicaRuntime;
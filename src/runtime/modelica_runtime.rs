@@ -1,8 +1,9 @@
 #![allow(unused_imports)]
-use crate::component::{ComponentError, ComponentResult};
+use crate::component::{Causality, ComponentError, ComponentResult, IOSpec, IOType, Variability};
+use super::messages::{self, RuntimeMessage};
+use super::native::{self, NativeModel};
+use crate::observer::{self, Observable, Observers};
 use std::collections::HashMap;
-// Include the generated bindings
-include!(concat!(env!("OUT_DIR"), "/simplethermalmvp_bindings.rs"));
 
 /// Safe wrapper around OpenModelica runtime structures
 /// 
@@ -27,6 +28,7 @@ include!(concat!(env!("OUT_DIR"), "/simplethermalmvp_bindings.rs"));
 /// ```
 pub struct ModelicaRuntime {
     component_name: String,
+    model: &'static NativeModel,
     // Currently using simplified simulation
     // TODO: Replace with actual OpenModelica pointers when ready
     // data: *mut DATA,
@@ -35,7 +37,15 @@ pub struct ModelicaRuntime {
     // Temporary: Rust-based state
     real_vars: std::collections::HashMap<String, f64>,
     bool_vars: std::collections::HashMap<String, bool>,
+    variables: Vec<IOSpec>,
+    initialized: bool,
     time: f64,
+    solver_calls: u64,
+    /// Runtime messages reported by the last initialize, step or reset
+    messages: Vec<RuntimeMessage>,
+    /// Variable values before the current step
+    snapshot: Snapshot,
+    observers: Observers<ModelicaRuntime>,
 }
 
 impl ModelicaRuntime {
@@ -68,45 +78,198 @@ impl ModelicaRuntime {
         }
         
         // TODO: Initialize actual OpenModelica runtime
-        // For now, create simplified runtime
+        // For now, use the pure-Rust implementation of the model
         
+        let model = native::find(component_name)
+            .ok_or_else(|| ComponentError::InitializationFailed(
+                format!("Unknown component: {}", component_name)
+            ))?;
+        let variables = model.variables().to_vec();
+        
+        #[cfg(feature = "openmodelica")]
+        messages::install();
+        
+        // Populate storage from start values
         let mut real_vars = std::collections::HashMap::new();
         let mut bool_vars = std::collections::HashMap::new();
-        
-        // Initialize based on component type
-        match component_name {
-            "SimpleThermalMVP" => {
-                // Initialize state variables
-                real_vars.insert("roomTemp".to_string(), 250.0);
-                real_vars.insert("temperature".to_string(), 250.0);
-                real_vars.insert("heaterStatus".to_string(), 0.0);
-                
-                // Initialize parameters
-                real_vars.insert("roomCapacity".to_string(), 1000.0);
-                real_vars.insert("ambientTemp".to_string(), 250.0);
-                real_vars.insert("heaterPower".to_string(), 500.0);
-                real_vars.insert("lossCoefficient".to_string(), 2.0);
-                
-                // Initialize inputs
-                bool_vars.insert("heaterOn".to_string(), false);
-            }
-            _ => {
-                return Err(ComponentError::InitializationFailed(
-                    format!("Unknown component: {}", component_name)
-                ));
+        for var in variables.iter().filter(|v| v.causality != Causality::Independent) {
+            let start = var.start.unwrap_or(0.0);
+            match var.io_type {
+                IOType::Boolean => { bool_vars.insert(var.name.clone(), start != 0.0); }
+                IOType::Real | IOType::Integer => { real_vars.insert(var.name.clone(), start); }
             }
         }
         
         Ok(Self {
             component_name: component_name.to_string(),
+            model,
             real_vars,
             bool_vars,
+            variables,
+            initialized: false,
             time: 0.0,
+            solver_calls: 0,
+            messages: Vec::new(),
+            snapshot: Snapshot::default(),
+            observers: Observers::new(),
         })
     }
     
+    /// Creates a new ModelicaRuntime with parameter overrides
+    /// 
+    /// Equivalent to OpenModelica's `-override`: the overrides are applied
+    /// before initialization, so fixed parameters and the start values
+    /// derived from them take the new values.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if the component is unknown, if a name is not a
+    /// parameter of the model, or if a value is not finite.
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// # use modelica_rust_ffi::ModelicaRuntime;
+    /// use std::collections::HashMap;
+    /// 
+    /// let mut parameters = HashMap::new();
+    /// parameters.insert("ambientTemp".to_string(), 280.0);
+    /// parameters.insert("heaterPower".to_string(), 750.0);
+    /// 
+    /// let runtime = ModelicaRuntime::with_parameters("SimpleThermalMVP", parameters)?;
+    /// assert_eq!(runtime.get_real_variable("temperature")?, 280.0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_parameters(component_name: &str, parameters: HashMap<String, f64>) -> ComponentResult<Self> {
+        let mut runtime = Self::new(component_name)?;
+        for (name, value) in parameters {
+            runtime.set_parameter(&name, value)?;
+        }
+        runtime.apply_start_values()?;
+        Ok(runtime)
+    }
+    
+    /// Initializes the simulation from the current parameter values
+    /// 
+    /// States are set to their start values and fixed parameters become
+    /// read-only until the next `reset`. Calling `step` on a runtime that
+    /// has not been initialized freezes fixed parameters without touching
+    /// the states.
+    pub fn initialize(&mut self) -> ComponentResult<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("initialize", model = self.model.name()).entered();
+        
+        self.apply_start_values()?;
+        self.time = 0.0;
+        self.initialized = true;
+        Ok(())
+    }
+    
+    /// Returns true once `initialize` or `step` has been called since
+    /// creation or the last `reset`
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+    
+    /// Returns metadata for every variable of the model, including time
+    pub fn variables(&self) -> &[IOSpec] {
+        &self.variables
+    }
+    
+    /// Returns metadata for a single variable
+    pub fn variable(&self, name: &str) -> Option<&IOSpec> {
+        self.variables.iter().find(|v| v.name == name)
+    }
+    
+    /// Returns metadata for every parameter of the model
+    pub fn parameters(&self) -> impl Iterator<Item = &IOSpec> {
+        self.variables.iter().filter(|v| v.causality == Causality::Parameter)
+    }
+    
+    /// Gets the value of a parameter
+    /// 
+    /// # Errors
+    /// 
+    /// Returns `ComponentError::VariableNotFound` if `name` is not a parameter
+    pub fn get_parameter(&self, name: &str) -> ComponentResult<f64> {
+        self.parameter_spec(name)?;
+        self.get_real_variable(name)
+    }
+    
+    /// Sets the value of a parameter
+    /// 
+    /// # Errors
+    /// 
+    /// Returns error if:
+    /// - `name` is not a parameter of the model
+    /// - The parameter is fixed and the runtime is already initialized
+    /// - Value is not finite
+    /// 
+    /// # Examples
+    /// 
+    /// ```no_run
+    /// # use modelica_rust_ffi::ModelicaRuntime;
+    /// # let mut runtime = ModelicaRuntime::new("SimpleThermalMVP")?;
+    /// runtime.initialize()?;
+    /// 
+    /// // Tunable parameters can change between steps
+    /// runtime.set_parameter("heaterPower", 900.0)?;
+    /// 
+    /// // Fixed parameters need a reset first
+    /// assert!(runtime.set_parameter("roomCapacity", 2000.0).is_err());
+    /// runtime.reset()?;
+    /// runtime.set_parameter("roomCapacity", 2000.0)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_parameter(&mut self, name: &str, value: f64) -> ComponentResult<()> {
+        let spec = self.parameter_spec(name)?;
+        if spec.variability == Variability::Fixed && self.initialized {
+            return Err(ComponentError::FixedParameter(name.to_string()));
+        }
+        
+        if !value.is_finite() {
+            return Err(ComponentError::InvalidInput(
+                format!("Value for '{}' must be finite, got: {}", name, value)
+            ));
+        }
+        spec.check_value(value)?;
+        
+        self.real_vars.insert(name.to_string(), value);
+        Ok(())
+    }
+    
+    /// Internal: Look up parameter metadata by name
+    fn parameter_spec(&self, name: &str) -> ComponentResult<&IOSpec> {
+        self.variable(name)
+            .filter(|v| v.causality == Causality::Parameter)
+            .ok_or_else(|| ComponentError::VariableNotFound(name.to_string()))
+    }
+    
+    /// Internal: Set states and inputs to their start values
+    fn apply_start_values(&mut self) -> ComponentResult<()> {
+        let starts: Vec<(String, IOType, f64)> = self.variables.iter()
+            .filter(|v| !matches!(v.causality, Causality::Parameter | Causality::Independent))
+            .map(|v| (v.name.clone(), v.io_type, v.start.unwrap_or(0.0)))
+            .collect();
+        for (name, io_type, start) in starts {
+            match io_type {
+                IOType::Boolean => self.set_bool_variable(&name, start != 0.0)?,
+                IOType::Real | IOType::Integer => self.set_real_variable(&name, start)?,
+            }
+        }
+        
+        // Start values that depend on parameters
+        let model = self.model;
+        let (result, messages) = messages::scope(model.name(), self.time, || (model.start)(self));
+        self.messages = messages;
+        result
+    }
+    
     /// Advances the simulation by the given time step
     /// 
+    /// Callbacks registered through `Observable` run before and after the
+    /// step, or with the error if it fails.
+    /// 
     /// # Arguments
     /// 
     /// * `dt` - Time step in seconds (must be positive and finite)
@@ -116,7 +279,13 @@ impl ModelicaRuntime {
     /// Returns `ComponentError::StepFailed` if:
     /// - Time step is invalid (negative, zero, infinite, or NaN)
     /// - Simulation equations fail to converge
-    /// - Runtime error occurs
+    /// 
+    /// Once the OpenModelica-generated step is called, a runtime abort
+    /// (failed `assert`, solver error) returns `ComponentError::RuntimeError`
+    /// with the assertion message; the pure-Rust models never abort.
+    /// 
+    /// A failed step leaves every variable and the time as they were, so
+    /// the runtime can be stepped again, e.g. after changing an input.
     /// 
     /// # Examples
     /// 
@@ -128,66 +297,42 @@ impl ModelicaRuntime {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn step(&mut self, dt: f64) -> ComponentResult<()> {
-        // Validate timestep
-        if dt <= 0.0 || !dt.is_finite() {
-            return Err(ComponentError::StepFailed(
-                format!("Invalid timestep: {}. Must be positive and finite.", dt)
-            ));
-        }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("step", model = self.model.name(), time = self.time, dt).entered();
         
-        // TODO: Call actual OpenModelica step function
-        // For now, implement simple Euler integration
-        
-        match self.component_name.as_str() {
-            "SimpleThermalMVP" => {
-                self.step_simple_thermal(dt)?;
+        observer::observe_step(self, self.time, dt, |runtime| {
+            // Validate timestep
+            if dt <= 0.0 || !dt.is_finite() {
+                return Err((None, ComponentError::StepFailed(
+                    format!("Invalid timestep: {}. Must be positive and finite.", dt)
+                )));
             }
-            _ => {
-                return Err(ComponentError::StepFailed(
-                    format!("Component {} has no step implementation", self.component_name)
-                ));
+            
+            // Fixed parameters are frozen from the first step on
+            runtime.initialized = true;
+            
+            // TODO: Call the generated OpenModelica step function through
+            // guard::call once the runtime owns DATA / threadData_t. For
+            // now, step the pure-Rust implementation of the model
+            runtime.solver_calls += 1;
+            runtime.snapshot.save(&runtime.real_vars, &runtime.bool_vars);
+            let model = runtime.model;
+            let (result, messages) = messages::scope(model.name(), runtime.time, || (model.step)(runtime, dt));
+            runtime.messages = messages;
+            if let Err(error) = result {
+                runtime.snapshot.restore(&mut runtime.real_vars, &mut runtime.bool_vars);
+                return Err((None, error));
             }
-        }
-        
-        self.time += dt;
-        Ok(())
-    }
-    
-    /// Internal: Step SimpleThermalMVP simulation
-    fn step_simple_thermal(&mut self, dt: f64) -> ComponentResult<()> {
-        // Get state and parameters
-        let room_temp = self.get_real_variable("roomTemp")?;
-        let room_capacity = self.get_real_variable("roomCapacity")?;
-        let ambient_temp = self.get_real_variable("ambientTemp")?;
-        let heater_power = self.get_real_variable("heaterPower")?;
-        let loss_coefficient = self.get_real_variable("lossCoefficient")?;
-        let heater_on = self.get_bool_variable("heaterOn")?;
-        
-        // Calculate heating and losses
-        let heating = if heater_on { heater_power } else { 0.0 };
-        let losses = loss_coefficient * (room_temp - ambient_temp);
-        
-        // Euler integration: dT/dt = (heating - losses) / capacity
-        let d_temp = (heating - losses) / room_capacity * dt;
-        let new_temp = room_temp + d_temp;
-        
-        // Validate result
-        if !new_temp.is_finite() {
-            return Err(ComponentError::StepFailed(
-                "Temperature calculation resulted in non-finite value".to_string()
-            ));
-        }
-        
-        // Update state
-        self.set_real_variable("roomTemp", new_temp)?;
-        self.set_real_variable("temperature", new_temp)?;
-        self.set_real_variable("heaterStatus", if heater_on { 1.0 } else { 0.0 })?;
-        
-        Ok(())
+            
+            runtime.time += dt;
+            Ok(())
+        })
     }
     
     /// Gets the value of a real variable
     /// 
+    /// The independent variable `time` can be read like any other.
+    /// 
     /// # Arguments
     /// 
     /// * `name` - Variable name
@@ -206,6 +351,9 @@ impl ModelicaRuntime {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn get_real_variable(&self, name: &str) -> ComponentResult<f64> {
+        if name == "time" {
+            return Ok(self.time);
+        }
         self.real_vars.get(name)
             .copied()
             .ok_or_else(|| ComponentError::VariableNotFound(name.to_string()))
@@ -222,8 +370,9 @@ impl ModelicaRuntime {
     /// 
     /// Returns error if:
     /// - Variable doesn't exist
+    /// - Variable is a fixed parameter and the runtime is initialized
     /// - Value is not finite (NaN or infinite)
-    /// - Value is outside valid bounds (if bounds exist)
+    /// - Value is outside the variable's `min`/`max`
     /// 
     /// # Examples
     /// 
@@ -246,17 +395,14 @@ impl ModelicaRuntime {
             return Err(ComponentError::VariableNotFound(name.to_string()));
         }
         
-        // TODO: Add bounds checking based on Modelica variable attributes
-        // For now, just basic sanity checks
-        match name {
-            "temperature" | "roomTemp" => {
-                if value < 0.0 || value > 1000.0 {
-                    return Err(ComponentError::BoundsCheckFailed(
-                        name.to_string(), value, 0.0, 1000.0
-                    ));
-                }
-            }
-            _ => {}
+        // Parameters go through the same rules as set_parameter
+        if self.parameter_spec(name).is_ok() {
+            return self.set_parameter(name, value);
+        }
+        
+        // Bounds from the Modelica min/max attributes
+        if let Some(spec) = self.variable(name) {
+            spec.check_value(value)?;
         }
         
         self.real_vars.insert(name.to_string(), value);
@@ -312,8 +458,27 @@ impl ModelicaRuntime {
         self.time
     }
     
+    /// Runtime messages (warnings, failed assertions) reported during the
+    /// last `initialize`, `step` or `reset`
+    ///
+    /// They are also reported as they happen: as `tracing` events with the
+    /// `tracing` feature, otherwise warnings and worse on stderr.
+    pub fn messages(&self) -> &[RuntimeMessage] {
+        &self.messages
+    }
+    
+    /// Number of calls into the model's step function since creation
+    ///
+    /// Not cleared by `reset`.
+    pub fn solver_calls(&self) -> u64 {
+        self.solver_calls
+    }
+    
     /// Resets the simulation to initial conditions
     /// 
+    /// Parameters keep their current values. Fixed parameters become
+    /// writable again until the next `initialize` or `step`.
+    /// 
     /// # Examples
     /// 
     /// ```no_run
@@ -325,18 +490,13 @@ impl ModelicaRuntime {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn reset(&mut self) -> ComponentResult<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("reset", model = self.model.name()).entered();
+        
         // Reset to initial state
-        match self.component_name.as_str() {
-            "SimpleThermalMVP" => {
-                let ambient = self.real_vars.get("ambientTemp").copied().unwrap_or(250.0);
-                self.set_real_variable("roomTemp", ambient)?;
-                self.set_real_variable("temperature", ambient)?;
-                self.set_real_variable("heaterStatus", 0.0)?;
-                self.set_bool_variable("heaterOn", false)?;
-            }
-            _ => {}
-        }
+        self.apply_start_values()?;
         
+        self.initialized = false;
         self.time = 0.0;
         Ok(())
     }
@@ -345,6 +505,77 @@ impl ModelicaRuntime {
     pub fn component_name(&self) -> &str {
         &self.component_name
     }
+    
+    /// Gets the model category (e.g. "Thermal")
+    pub fn category(&self) -> &str {
+        self.model.category()
+    }
+    
+    /// Category and variable table of a model, from its static description
+    ///
+    /// Reads the same table `new` uses, without creating an instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::UnknownComponentType` for an unknown model.
+    pub fn model_description(model_name: &str) -> ComponentResult<(&'static str, &'static [IOSpec])> {
+        let model = native::find(model_name)
+            .ok_or_else(|| ComponentError::UnknownComponentType(model_name.to_string()))?;
+        Ok((model.category(), model.variables()))
+    }
+    
+    /// Names of all models this runtime can instantiate
+    pub fn available_models() -> Vec<&'static str> {
+        native::MODELS.iter().map(|m| m.name()).collect()
+    }
+}
+
+/// Variable values saved before a step, to roll back a failed one
+///
+/// Kept by name, independent of the maps' iteration order. Entries are
+/// updated in place, so saving does not allocate after the first step.
+#[derive(Default)]
+struct Snapshot {
+    reals: HashMap<String, f64>,
+    bools: HashMap<String, bool>,
+}
+
+impl Snapshot {
+    fn save(&mut self, reals: &HashMap<String, f64>, bools: &HashMap<String, bool>) {
+        Self::save_values(&mut self.reals, reals);
+        Self::save_values(&mut self.bools, bools);
+    }
+
+    fn restore(&self, reals: &mut HashMap<String, f64>, bools: &mut HashMap<String, bool>) {
+        Self::restore_values(&self.reals, reals);
+        Self::restore_values(&self.bools, bools);
+    }
+
+    fn save_values<T: Copy>(saved: &mut HashMap<String, T>, values: &HashMap<String, T>) {
+        saved.retain(|name, _| values.contains_key(name));
+        for (name, value) in values {
+            match saved.get_mut(name) {
+                Some(slot) => *slot = *value,
+                None => {
+                    saved.insert(name.clone(), *value);
+                }
+            }
+        }
+    }
+
+    fn restore_values<T: Copy>(saved: &HashMap<String, T>, values: &mut HashMap<String, T>) {
+        for (name, value) in values.iter_mut() {
+            if let Some(saved) = saved.get(name) {
+                *value = *saved;
+            }
+        }
+    }
+}
+
+impl Observable for ModelicaRuntime {
+    fn observers(&mut self) -> &mut Observers<Self> {
+        &mut self.observers
+    }
 }
 
 impl Drop for ModelicaRuntime {
@@ -365,6 +596,7 @@ impl std::fmt::Debug for ModelicaRuntime {
             .field("time", &self.time)
             .field("real_vars_count", &self.real_vars.len())
             .field("bool_vars_count", &self.bool_vars.len())
+            .field("initialized", &self.initialized)
             .finish()
     }
 }
@@ -0,0 +1,110 @@
+//! Pure-Rust implementations of the Modelica models
+//!
+//! These are used when the crate is built without the `openmodelica`
+//! feature (and, until the C runtime is wired up, with it as well). Each
+//! model provides its variable table, the start values that depend on
+//! parameters, and an explicit Euler step.
+
+use crate::component::{ComponentError, ComponentResult, IOSpec};
+use super::ModelicaRuntime;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+/// A model implemented directly in Rust
+///
+/// The variable table lives in `models/<Name>.toml`, which build.rs also
+/// reads to generate the typed wrappers in `crate::models`.
+pub(crate) struct NativeModel {
+    /// Model description in TOML
+    description: &'static str,
+    parsed: OnceLock<ModelDescription>,
+    /// Sets start values that are computed from parameters
+    pub start: fn(&mut ModelicaRuntime) -> ComponentResult<()>,
+    /// Advances the model's states by `dt`
+    pub step: fn(&mut ModelicaRuntime, f64) -> ComponentResult<()>,
+}
+
+#[derive(Deserialize)]
+struct ModelDescription {
+    name: String,
+    category: String,
+    variables: Vec<IOSpec>,
+}
+
+impl NativeModel {
+    fn description(&self) -> &ModelDescription {
+        self.parsed.get_or_init(|| {
+            // build.rs parses the same file, so this cannot fail at runtime
+            toml::from_str(self.description).expect("model description is validated by build.rs")
+        })
+    }
+    
+    pub fn name(&self) -> &str {
+        &self.description().name
+    }
+    
+    pub fn category(&self) -> &str {
+        &self.description().category
+    }
+    
+    /// Variable table with causality, bounds and start values
+    pub fn variables(&self) -> &[IOSpec] {
+        &self.description().variables
+    }
+}
+
+/// Every model available without OpenModelica
+pub(crate) static MODELS: [NativeModel; 1] = [
+    NativeModel {
+        description: include_str!("../../models/SimpleThermalMVP.toml"),
+        parsed: OnceLock::new(),
+        start: simple_thermal_start,
+        step: simple_thermal_step,
+    },
+];
+
+/// Looks up a model by name
+pub(crate) fn find(name: &str) -> Option<&'static NativeModel> {
+    MODELS.iter().find(|m| m.name() == name)
+}
+
+// SimpleThermalMVP
+
+fn simple_thermal_start(rt: &mut ModelicaRuntime) -> ComponentResult<()> {
+    let ambient = rt.get_real_variable("ambientTemp")?;
+    rt.set_real_variable("roomTemp", ambient)?;
+    rt.set_real_variable("temperature", ambient)?;
+    Ok(())
+}
+
+fn simple_thermal_step(rt: &mut ModelicaRuntime, dt: f64) -> ComponentResult<()> {
+    // Get state and parameters
+    let room_temp = rt.get_real_variable("roomTemp")?;
+    let room_capacity = rt.get_real_variable("roomCapacity")?;
+    let ambient_temp = rt.get_real_variable("ambientTemp")?;
+    let heater_power = rt.get_real_variable("heaterPower")?;
+    let loss_coefficient = rt.get_real_variable("lossCoefficient")?;
+    let heater_on = rt.get_bool_variable("heaterOn")?;
+    
+    // Calculate heating and losses
+    let heating = if heater_on { heater_power } else { 0.0 };
+    let losses = loss_coefficient * (room_temp - ambient_temp);
+    
+    // Euler integration: dT/dt = (heating - losses) / capacity
+    let d_temp = (heating - losses) / room_capacity * dt;
+    let new_temp = room_temp + d_temp;
+    
+    // Validate result
+    if !new_temp.is_finite() {
+        return Err(ComponentError::StepFailed(
+            "Temperature calculation resulted in non-finite value".to_string()
+        ));
+    }
+    
+    // Update state
+    rt.set_real_variable("roomTemp", new_temp)?;
+    rt.set_real_variable("temperature", new_temp)?;
+    rt.set_real_variable("heaterStatus", if heater_on { 1.0 } else { 0.0 })?;
+    
+    Ok(())
+}
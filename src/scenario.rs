@@ -0,0 +1,358 @@
+use crate::component::{ComponentError, ComponentResult, IOType, SimulationComponent};
+use crate::factory::ComponentFactory;
+use crate::registry::ComponentRegistry;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Declarative description of a colony simulation
+///
+/// A scenario lists the component instances to create, how their
+/// outputs feed each other's inputs, the integration step and stop
+/// time, and any inputs that change at scheduled times. It can be
+/// loaded from TOML or JSON and turned into a ready-to-run
+/// `ComponentRegistry`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use modelica_rust_ffi::Scenario;
+///
+/// let scenario = Scenario::from_toml_str(r#"
+///     step_size = 0.1
+///     stop_time = 60.0
+///
+///     [[components]]
+///     type = "SimpleThermalMVP"
+///     name = "habitat_1"
+///     parameters = { heaterPower = 750.0 }
+///     initial = { heaterOn = true }
+///
+///     [[events]]
+///     time = 30.0
+///     target = "habitat_1.heaterOn"
+///     value = false
+/// "#)?;
+///
+/// let registry = scenario.run()?;
+/// let temp = registry.get_by_name("habitat_1").unwrap().get_output("temperature")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    /// Optional human-readable scenario name
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Integration step in seconds
+    pub step_size: f64,
+
+    /// Simulation end time in seconds; the last step is shortened if
+    /// needed to end exactly here
+    pub stop_time: f64,
+
+    /// Component instances to create
+    #[serde(default)]
+    pub components: Vec<ComponentSpec>,
+
+    /// Output-to-input connections, applied before every step
+    #[serde(default)]
+    pub connections: Vec<Connection>,
+
+    /// Input changes applied at fixed simulation times in `[0, stop_time]`
+    #[serde(default)]
+    pub events: Vec<ScheduledInput>,
+}
+
+/// A single component instance in a scenario
+#[derive(Debug, Clone, Deserialize)]
+pub struct ComponentSpec {
+    /// Component type, e.g. "SimpleThermalMVP"
+    #[serde(rename = "type")]
+    pub component_type: String,
+
+    /// Unique instance name within the registry
+    pub name: String,
+
+    /// Parameter overrides passed to the component's constructor
+    #[serde(default)]
+    pub parameters: HashMap<String, ScenarioValue>,
+
+    /// Initial values for states and inputs
+    #[serde(default)]
+    pub initial: HashMap<String, ScenarioValue>,
+}
+
+/// Connects an output of one component to an input of another
+///
+/// Both ends are written as `"component.variable"`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Connection {
+    pub from: String,
+    pub to: String,
+}
+
+/// An input value that is applied once simulation time reaches `time`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduledInput {
+    pub time: f64,
+    /// Target written as `"component.variable"`
+    pub target: String,
+    pub value: ScenarioValue,
+}
+
+/// A value in a scenario file, either real or boolean
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ScenarioValue {
+    Bool(bool),
+    Real(f64),
+}
+
+impl ScenarioValue {
+    /// Returns the value as a real number (booleans map to 0.0/1.0)
+    pub fn as_real(&self) -> f64 {
+        match *self {
+            ScenarioValue::Bool(b) => if b { 1.0 } else { 0.0 },
+            ScenarioValue::Real(v) => v,
+        }
+    }
+
+    /// Returns the value as a boolean (reals are true when non-zero)
+    pub fn as_bool(&self) -> bool {
+        match *self {
+            ScenarioValue::Bool(b) => b,
+            ScenarioValue::Real(v) => v != 0.0,
+        }
+    }
+}
+
+impl Scenario {
+    /// Loads a scenario from a file, choosing the format by extension
+    ///
+    /// Files ending in `.json` are parsed as JSON, everything else as TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ComponentError::InvalidScenario` if the file cannot be read
+    /// or parsed, or if the scenario fails validation.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> ComponentResult<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            ComponentError::InvalidScenario(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json_str(&text),
+            _ => Self::from_toml_str(&text),
+        }
+    }
+
+    /// Parses a scenario from TOML text
+    pub fn from_toml_str(text: &str) -> ComponentResult<Self> {
+        let scenario: Self = toml::from_str(text)
+            .map_err(|e| ComponentError::InvalidScenario(e.to_string()))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Parses a scenario from JSON text
+    pub fn from_json_str(text: &str) -> ComponentResult<Self> {
+        let scenario: Self = serde_json::from_str(text)
+            .map_err(|e| ComponentError::InvalidScenario(e.to_string()))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Checks step size, stop time, that every connection and event
+    /// refers to a declared component and that events fall within the run
+    pub fn validate(&self) -> ComponentResult<()> {
+        if self.step_size <= 0.0 || !self.step_size.is_finite() {
+            return Err(ComponentError::InvalidScenario(
+                format!("Invalid step_size: {}. Must be positive and finite.", self.step_size)
+            ));
+        }
+
+        if self.stop_time < 0.0 || !self.stop_time.is_finite() {
+            return Err(ComponentError::InvalidScenario(
+                format!("Invalid stop_time: {}. Must be non-negative and finite.", self.stop_time)
+            ));
+        }
+
+        let known = |component: &str| self.components.iter().any(|c| c.name == component);
+
+        for connection in &self.connections {
+            for end in [&connection.from, &connection.to] {
+                let (component, _) = split_target(end)?;
+                if !known(component) {
+                    return Err(ComponentError::InvalidScenario(
+                        format!("Connection refers to unknown component '{}'", component)
+                    ));
+                }
+            }
+        }
+
+        for event in &self.events {
+            let (component, _) = split_target(&event.target)?;
+            if !known(component) {
+                return Err(ComponentError::InvalidScenario(
+                    format!("Event refers to unknown component '{}'", component)
+                ));
+            }
+            if !event.time.is_finite() {
+                return Err(ComponentError::InvalidScenario(
+                    format!("Invalid event time for '{}': {}", event.target, event.time)
+                ));
+            }
+            if event.time < 0.0 || event.time > self.stop_time + self.epsilon() {
+                return Err(ComponentError::InvalidScenario(format!(
+                    "Event for '{}' at t = {} s is outside the run [0, {}] s",
+                    event.target, event.time, self.stop_time
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates and initializes every component using the built-in
+    /// component types
+    pub fn build_registry(&self) -> ComponentResult<ComponentRegistry> {
+        self.build_registry_with(&ComponentFactory::with_builtins())
+    }
+
+    /// Creates and initializes every component using `factory` to resolve
+    /// component types
+    pub fn build_registry_with(&self, factory: &ComponentFactory) -> ComponentResult<ComponentRegistry> {
+        let mut registry = ComponentRegistry::new();
+
+        for spec in &self.components {
+            let parameters: HashMap<String, f64> = spec.parameters.iter()
+                .map(|(name, value)| (name.clone(), value.as_real()))
+                .collect();
+
+            let mut component = factory.create(&spec.component_type, &parameters)?;
+            component.initialize()?;
+
+            for (name, value) in &spec.initial {
+                apply_value(component.as_mut(), name, *value)?;
+            }
+
+            registry.add(spec.name.clone(), component)?;
+        }
+
+        Ok(registry)
+    }
+
+    /// Builds the registry and runs it until `stop_time`
+    pub fn run(&self) -> ComponentResult<ComponentRegistry> {
+        let mut registry = self.build_registry()?;
+        self.run_with(&mut registry, |_, _| Ok(()))?;
+        Ok(registry)
+    }
+
+    /// Runs an already built registry until `stop_time`
+    ///
+    /// `on_step` is called once with the initial state at time 0 and then
+    /// after every step with the new simulation time. If `stop_time` is not
+    /// a multiple of `step_size`, a final shorter step ends the run exactly
+    /// at `stop_time`.
+    pub fn run_with<F>(&self, registry: &mut ComponentRegistry, mut on_step: F) -> ComponentResult<()>
+    where
+        F: FnMut(f64, &ComponentRegistry) -> ComponentResult<()>,
+    {
+        let mut events: Vec<&ScheduledInput> = self.events.iter().collect();
+        events.sort_by(|a, b| a.time.total_cmp(&b.time));
+        let mut pending = events.into_iter().peekable();
+
+        let epsilon = self.epsilon();
+        let full_steps = ((self.stop_time + epsilon) / self.step_size).floor() as usize;
+        let remainder = self.stop_time - full_steps as f64 * self.step_size;
+        let steps = if remainder > epsilon { full_steps + 1 } else { full_steps };
+
+        for step in 0..=steps {
+            let time = step as f64 * self.step_size;
+
+            while let Some(event) = pending.next_if(|e| e.time <= time + epsilon) {
+                let (component, variable) = split_target(&event.target)?;
+                let target = registry.get_mut_by_name(component)
+                    .ok_or_else(|| ComponentError::InvalidScenario(
+                        format!("Component '{}' not found", component)
+                    ))?;
+                apply_value(target.as_mut(), variable, event.value)?;
+            }
+
+            self.propagate_connections(registry)?;
+
+            if step == 0 {
+                on_step(time, registry)?;
+            }
+            if step == steps {
+                break;
+            }
+
+            if step == full_steps {
+                // The partial last step
+                registry.step_all(remainder)?;
+                on_step(self.stop_time, registry)?;
+            } else {
+                registry.step_all(self.step_size)?;
+                on_step(time + self.step_size, registry)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Tolerance so events and the stop time are not missed on a step
+    /// boundary because of accumulated rounding
+    fn epsilon(&self) -> f64 {
+        self.step_size * 1e-6
+    }
+
+    /// Copies each connection's source output to its target input
+    fn propagate_connections(&self, registry: &mut ComponentRegistry) -> ComponentResult<()> {
+        for connection in &self.connections {
+            let (from_component, from_variable) = split_target(&connection.from)?;
+            let (to_component, to_variable) = split_target(&connection.to)?;
+
+            let value = registry.get_by_name(from_component)
+                .ok_or_else(|| ComponentError::InvalidScenario(
+                    format!("Component '{}' not found", from_component)
+                ))?
+                .get_output(from_variable)?;
+
+            let target = registry.get_mut_by_name(to_component)
+                .ok_or_else(|| ComponentError::InvalidScenario(
+                    format!("Component '{}' not found", to_component)
+                ))?;
+            apply_value(target.as_mut(), to_variable, ScenarioValue::Real(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits `"component.variable"` into its two parts
+fn split_target(target: &str) -> ComponentResult<(&str, &str)> {
+    target.split_once('.')
+        .filter(|(component, variable)| !component.is_empty() && !variable.is_empty())
+        .ok_or_else(|| ComponentError::InvalidScenario(
+            format!("Expected 'component.variable', got '{}'", target)
+        ))
+}
+
+/// Writes a value to a component, using its metadata to decide whether the
+/// variable is boolean
+fn apply_value(component: &mut dyn SimulationComponent, name: &str, value: ScenarioValue) -> ComponentResult<()> {
+    let is_bool = match value {
+        ScenarioValue::Bool(_) => true,
+        ScenarioValue::Real(_) => component.metadata().inputs.iter()
+            .any(|spec| spec.name == name && matches!(spec.io_type, IOType::Boolean)),
+    };
+
+    if is_bool {
+        component.set_bool_input(name, value.as_bool())
+    } else {
+        component.set_input(name, value.as_real())
+    }
+}
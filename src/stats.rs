@@ -0,0 +1,286 @@
+//! Per-component step profiling
+//!
+//! `ComponentRegistry::step_all` times every component's step, so the model
+//! that blows a frame budget can be found without an external profiler:
+//!
+//! ```no_run
+//! # use modelica_rust_ffi::ComponentRegistry;
+//! # let mut registry = ComponentRegistry::new();
+//! for _ in 0..1000 {
+//!     registry.step_all(0.1)?;
+//! }
+//! let stats = registry.stats();
+//! println!("{}", stats);
+//! if let Some(slowest) = stats.slowest() {
+//!     println!("slowest: {} (p99 {:?})", slowest.name, slowest.p99);
+//! }
+//! # Ok::<(), modelica_rust_ffi::ComponentError>(())
+//! ```
+//!
+//! Allocation counts are only collected when the application installs
+//! `CountingAllocator` as its global allocator:
+//!
+//! ```no_run
+//! use modelica_rust_ffi::stats::CountingAllocator;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator::new();
+//! ```
+//!
+//! With the `tracing` feature `step_all` also emits a `step_all` span and a
+//! `step` span per component at debug level.
+//!
+//! `wasm32-unknown-unknown` has no clock, so there every duration is zero;
+//! step, solver call and allocation counts are still collected.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Internal: Wall-clock stopwatch that reads zero on targets without a clock
+///
+/// `Instant::now` panics on `wasm32-unknown-unknown`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Stopwatch {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    started: std::time::Instant,
+}
+
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            started: std::time::Instant::now(),
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        return self.started.elapsed();
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        return Duration::ZERO;
+    }
+}
+
+/// Most recent step durations kept per component for the percentile
+pub const STATS_WINDOW: usize = 1024;
+
+/// Step statistics of one component
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepStats {
+    pub name: String,
+    /// Steps taken, including failed ones
+    pub steps: u64,
+    /// Wall-clock time spent in `step`
+    pub total: Duration,
+    pub min: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+    /// 99th percentile over the last `STATS_WINDOW` steps
+    pub p99: Duration,
+    /// Solver calls, for components that report them
+    pub solver_calls: Option<u64>,
+    /// Heap allocations, if `CountingAllocator` is installed
+    pub allocations: Option<u64>,
+}
+
+/// Step statistics of a registry, from `ComponentRegistry::stats`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegistryStats {
+    /// `step_all` calls
+    pub steps: u64,
+    /// Wall-clock time spent in `step_all`, observers included
+    pub total: Duration,
+    /// Sorted by name
+    pub components: Vec<StepStats>,
+}
+
+impl RegistryStats {
+    /// Statistics of the component called `name`
+    pub fn get(&self, name: &str) -> Option<&StepStats> {
+        self.components.iter().find(|stats| stats.name == name)
+    }
+
+    /// The component with the highest p99 step time
+    pub fn slowest(&self) -> Option<&StepStats> {
+        self.components.iter().filter(|stats| stats.steps > 0).max_by_key(|stats| stats.p99)
+    }
+
+    /// Mean wall-clock time of a `step_all` call
+    pub fn mean(&self) -> Duration {
+        mean(self.total, self.steps)
+    }
+}
+
+impl fmt::Display for RegistryStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<24} {:>8} {:>12} {:>12} {:>12} {:>12} {:>10} {:>10}",
+            "component", "steps", "min", "mean", "p99", "max", "solver", "allocs"
+        )?;
+        for stats in &self.components {
+            writeln!(
+                f,
+                "{:<24} {:>8} {:>12} {:>12} {:>12} {:>12} {:>10} {:>10}",
+                stats.name,
+                stats.steps,
+                format!("{:.1?}", stats.min),
+                format!("{:.1?}", stats.mean),
+                format!("{:.1?}", stats.p99),
+                format!("{:.1?}", stats.max),
+                stats.solver_calls.map_or("-".to_string(), |n| n.to_string()),
+                stats.allocations.map_or("-".to_string(), |n| n.to_string()),
+            )?;
+        }
+        write!(f, "{} steps, {:.1?} total, {:.1?} per step", self.steps, self.total, self.mean())
+    }
+}
+
+/// Internal: Accumulates one component's step timings
+#[derive(Debug, Clone)]
+pub(crate) struct StepTimer {
+    name: String,
+    steps: u64,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+    /// Ring buffer of the last `STATS_WINDOW` durations
+    recent: Vec<Duration>,
+    solver_calls: Option<u64>,
+    allocations: u64,
+}
+
+impl StepTimer {
+    pub(crate) fn new(name: String) -> Self {
+        Self {
+            name,
+            steps: 0,
+            total: Duration::ZERO,
+            min: Duration::MAX,
+            max: Duration::ZERO,
+            recent: Vec::new(),
+            solver_calls: None,
+            allocations: 0,
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn record(&mut self, duration: Duration, solver_calls: Option<u64>, allocations: u64) {
+        if self.recent.len() < STATS_WINDOW {
+            self.recent.push(duration);
+        } else {
+            self.recent[(self.steps % STATS_WINDOW as u64) as usize] = duration;
+        }
+        self.steps += 1;
+        self.total += duration;
+        self.min = self.min.min(duration);
+        self.max = self.max.max(duration);
+        if let Some(calls) = solver_calls {
+            *self.solver_calls.get_or_insert(0) += calls;
+        }
+        self.allocations += allocations;
+    }
+
+    pub(crate) fn reset(&mut self) {
+        *self = Self::new(std::mem::take(&mut self.name));
+    }
+
+    pub(crate) fn snapshot(&self) -> StepStats {
+        let mut recent = self.recent.clone();
+        recent.sort_unstable();
+        // Nearest-rank percentile
+        let p99 = match recent.len() {
+            0 => Duration::ZERO,
+            n => recent[(n * 99).div_ceil(100) - 1],
+        };
+
+        StepStats {
+            name: self.name.clone(),
+            steps: self.steps,
+            total: self.total,
+            min: if self.steps == 0 { Duration::ZERO } else { self.min },
+            mean: mean(self.total, self.steps),
+            max: self.max,
+            p99,
+            solver_calls: self.solver_calls,
+            allocations: counting_allocations().then_some(self.allocations),
+        }
+    }
+}
+
+fn mean(total: Duration, count: u64) -> Duration {
+    if count == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_secs_f64(total.as_secs_f64() / count as f64)
+}
+
+/// Global allocator that counts allocations per thread for `StepStats`
+///
+/// Forwards to the system allocator.
+pub struct CountingAllocator {
+    _private: (),
+}
+
+impl CountingAllocator {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl Default for CountingAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+    // Fails only while the thread is shutting down
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// True once `CountingAllocator` has served an allocation
+pub fn counting_allocations() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Allocations made on this thread so far; 0 without `CountingAllocator`
+pub fn thread_allocations() -> u64 {
+    ALLOCATIONS.try_with(Cell::get).unwrap_or(0)
+}
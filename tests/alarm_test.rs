@@ -0,0 +1,179 @@
+use modelica_rust_ffi::alarm::{Alarm, AlarmEventKind, AlarmMonitor};
+use modelica_rust_ffi::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Variables set directly by the test
+#[derive(Default)]
+struct Values(HashMap<String, f64>);
+
+impl Values {
+    fn set(&mut self, name: &str, value: f64) -> &mut Self {
+        self.0.insert(name.to_string(), value);
+        self
+    }
+}
+
+impl RecordSource for Values {
+    fn recordable_variables(&self) -> Vec<IOSpec> {
+        Vec::new()
+    }
+
+    fn read_variable(&self, name: &str) -> ComponentResult<f64> {
+        self.0.get(name).copied().ok_or_else(|| ComponentError::VariableNotFound(name.to_string()))
+    }
+}
+
+fn kinds(monitor: &mut AlarmMonitor, time: f64, values: &Values) -> Vec<AlarmEventKind> {
+    monitor.evaluate(time, values).unwrap().into_iter().map(|event| event.kind).collect()
+}
+
+#[test]
+fn test_alarm_parsing() {
+    let alarm = Alarm::new("fault", "heaterStatus == 1 and temperature > 310").unwrap().in_component("habitat_1");
+    assert_eq!(alarm.variables(), ["habitat_1.heaterStatus", "habitat_1.temperature"]);
+
+    assert!(Alarm::new("a", "not (o2 >= 19.5 and o2 <= 23.5) or co2 > 5e-1 for 2 min").is_ok());
+    assert!(Alarm::new("a", "x < -40 || !(y != true)").is_ok());
+
+    for invalid in ["", "x <", "x < 1 and", "(x < 1", "x 1", "x < 1 for", "x < 1 for 5 days", "x < 1 y", "x # 1"] {
+        assert!(
+            matches!(Alarm::new("a", invalid), Err(ComponentError::InvalidInput(_))),
+            "'{}' should not parse", invalid
+        );
+    }
+}
+
+#[test]
+fn test_alarm_logic() {
+    let mut monitor = AlarmMonitor::new();
+    monitor.add(Alarm::new("band", "not (o2 >= 19.5 and o2 <= 23.5) or co2 > 0.5").unwrap()).unwrap();
+    assert!(monitor.add(Alarm::new("band", "o2 < 1").unwrap()).is_err());
+
+    let mut values = Values::default();
+    values.set("o2", 21.0).set("co2", 0.1);
+    assert!(kinds(&mut monitor, 0.0, &values).is_empty());
+
+    values.set("o2", 18.0);
+    assert_eq!(kinds(&mut monitor, 1.0, &values), [AlarmEventKind::Raised]);
+    assert!(monitor.is_active("band"));
+
+    values.set("o2", 21.0).set("co2", 0.6);
+    assert!(kinds(&mut monitor, 2.0, &values).is_empty());
+
+    values.set("co2", 0.4);
+    assert_eq!(kinds(&mut monitor, 3.0, &values), [AlarmEventKind::Cleared]);
+    assert_eq!(monitor.active().count(), 0);
+
+    values.0.remove("co2");
+    assert!(matches!(monitor.evaluate(4.0, &values), Err(ComponentError::VariableNotFound(_))));
+}
+
+#[test]
+fn test_alarm_debounce() {
+    let mut monitor = AlarmMonitor::new();
+    monitor.add(Alarm::new("freezing", "temperature < 273.15 for 60 s").unwrap().with_clear_delay(10.0)).unwrap();
+
+    let mut values = Values::default();
+    values.set("temperature", 270.0);
+    assert!(kinds(&mut monitor, 0.0, &values).is_empty());
+    assert!(kinds(&mut monitor, 30.0, &values).is_empty());
+
+    // A brief recovery restarts the delay
+    values.set("temperature", 280.0);
+    assert!(kinds(&mut monitor, 40.0, &values).is_empty());
+    values.set("temperature", 270.0);
+    assert!(kinds(&mut monitor, 50.0, &values).is_empty());
+    assert!(kinds(&mut monitor, 100.0, &values).is_empty());
+
+    let events = monitor.evaluate(110.0, &values).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].time, events[0].since), (110.0, 50.0));
+    assert_eq!(events[0].alarm, "freezing");
+
+    values.set("temperature", 280.0);
+    assert!(kinds(&mut monitor, 115.0, &values).is_empty());
+    assert_eq!(kinds(&mut monitor, 125.0, &values), [AlarmEventKind::Cleared]);
+}
+
+#[test]
+fn test_alarm_hysteresis() {
+    let mut monitor = AlarmMonitor::new();
+    monitor.add(Alarm::new("hot", "temperature > 310").unwrap().with_hysteresis(2.0)).unwrap();
+
+    let mut values = Values::default();
+    let mut events = Vec::new();
+    for (time, temperature) in [311.0, 309.5, 310.5, 308.5, 307.5, 309.5, 310.5].into_iter().enumerate() {
+        values.set("temperature", temperature);
+        events.extend(kinds(&mut monitor, time as f64, &values));
+    }
+    // Raised at 311; stays raised until below 308; raised again above 310
+    assert_eq!(events, [AlarmEventKind::Raised, AlarmEventKind::Cleared, AlarmEventKind::Raised]);
+}
+
+#[test]
+fn test_alarm_monitor_on_registry() {
+    let mut registry = ComponentRegistry::new();
+    let mut thermal = SimpleThermalComponent::with_parameters(
+        &[("roomCapacity".to_string(), 100.0)].into_iter().collect(),
+    ).unwrap();
+    thermal.initialize().unwrap();
+    thermal.set_bool_input("heaterOn", true).unwrap();
+    registry.add("habitat_1".to_string(), Box::new(thermal)).unwrap();
+
+    let mut monitor = AlarmMonitor::new();
+    monitor.add(Alarm::new("overheat", "heaterStatus == 1 and temperature > 260 for 2 s").unwrap()
+        .in_component("habitat_1")).unwrap();
+    let monitor = Arc::new(Mutex::new(monitor));
+
+    let mut missing = AlarmMonitor::new();
+    missing.add(Alarm::new("x", "habitat_9.temperature > 1").unwrap()).unwrap();
+    assert!(AlarmMonitor::attach(&Arc::new(Mutex::new(missing)), &mut registry, |_| {}).is_err());
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    AlarmMonitor::attach(&monitor, &mut registry, move |event| sink.lock().unwrap().push(event.clone())).unwrap();
+
+    for _ in 0..10 {
+        registry.step_all(1.0).unwrap();
+    }
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, AlarmEventKind::Raised);
+    assert_eq!(events[0].time - events[0].since, 2.0);
+    assert!(monitor.lock().unwrap().is_active("overheat"));
+}
+
+#[test]
+fn test_alarm_monitor_reports_errors() {
+    let mut registry = ComponentRegistry::new();
+    for name in ["habitat_1", "habitat_2"] {
+        let mut thermal = SimpleThermalComponent::new().unwrap();
+        thermal.initialize().unwrap();
+        registry.add(name.to_string(), Box::new(thermal)).unwrap();
+    }
+
+    let mut monitor = AlarmMonitor::new();
+    monitor.add(Alarm::new("habitat_1 cold", "habitat_1.temperature < 300").unwrap()).unwrap();
+    monitor.add(Alarm::new("habitat_2 cold", "habitat_2.temperature < 300").unwrap()).unwrap();
+    let monitor = Arc::new(Mutex::new(monitor));
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    AlarmMonitor::attach(&monitor, &mut registry, move |event| sink.lock().unwrap().push(event.clone())).unwrap();
+
+    registry.step_all(1.0).unwrap();
+    registry.remove(registry.id_by_name("habitat_1").unwrap()).unwrap();
+    registry.step_all(1.0).unwrap();
+
+    let events = events.lock().unwrap();
+    let kinds: Vec<(&str, &AlarmEventKind)> = events.iter().map(|e| (e.alarm.as_str(), &e.kind)).collect();
+    assert_eq!(kinds[..2], [("habitat_1 cold", &AlarmEventKind::Raised), ("habitat_2 cold", &AlarmEventKind::Raised)]);
+    // The alarm on the removed component fails; the other is still evaluated
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[2].alarm, "habitat_1 cold");
+    assert_eq!(events[2].time, 2.0);
+    assert!(matches!(&events[2].kind, AlarmEventKind::Error(message) if message.contains("habitat_1")));
+    assert!(monitor.lock().unwrap().is_active("habitat_2 cold"));
+}
@@ -0,0 +1,60 @@
+//! Separate test binary: installs `CountingAllocator` for the whole process
+use modelica_rust_ffi::stats::CountingAllocator;
+use modelica_rust_ffi::*;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator::new();
+
+/// Allocates a buffer every step
+struct Buffered {
+    history: Vec<Vec<f64>>,
+}
+
+impl SimulationComponent for Buffered {
+    fn component_type(&self) -> &str {
+        "Buffered"
+    }
+
+    fn initialize(&mut self) -> ComponentResult<()> {
+        Ok(())
+    }
+
+    fn set_input(&mut self, name: &str, _value: f64) -> ComponentResult<()> {
+        Err(ComponentError::VariableNotFound(name.to_string()))
+    }
+
+    fn set_bool_input(&mut self, name: &str, _value: bool) -> ComponentResult<()> {
+        Err(ComponentError::VariableNotFound(name.to_string()))
+    }
+
+    fn get_output(&self, name: &str) -> ComponentResult<f64> {
+        Err(ComponentError::VariableNotFound(name.to_string()))
+    }
+
+    fn step(&mut self, dt: f64) -> ComponentResult<()> {
+        self.history.push(vec![dt; 16]);
+        Ok(())
+    }
+
+    fn reset(&mut self) -> ComponentResult<()> {
+        self.history.clear();
+        Ok(())
+    }
+
+    fn metadata(&self) -> ComponentMetadata {
+        ComponentMetadata::from_variables("Buffered", "Test", &[])
+    }
+}
+
+#[test]
+fn test_allocation_counts() {
+    let mut registry = ComponentRegistry::new();
+    let buffered = Buffered { history: Vec::with_capacity(100) };
+    registry.add("buffered".to_string(), Box::new(buffered)).unwrap();
+    for _ in 0..10 {
+        registry.step_all(0.1).unwrap();
+    }
+
+    let stats = registry.stats();
+    assert_eq!(stats.get("buffered").unwrap().allocations, Some(10));
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "arrow")]
+
+use arrow_array::{Array, Float64Array};
+use modelica_rust_ffi::*;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+fn habitats(count: usize) -> ComponentRegistry {
+    let mut registry = ComponentRegistry::new();
+    for i in 1..=count {
+        let mut thermal = SimpleThermalComponent::new().unwrap();
+        thermal.initialize().unwrap();
+        thermal.set_bool_input("heaterOn", true).unwrap();
+        registry.add(format!("habitat_{}", i), Box::new(thermal)).unwrap();
+    }
+    registry
+}
+
+fn run<S: ResultSink>(sink: S, steps: usize) -> S {
+    let mut registry = habitats(3);
+    let mut recorder = Recorder::new(sink).select("*.temperature");
+    recorder.record(0.0, &registry).unwrap();
+    for step in 1..=steps {
+        registry.step_all(0.1).unwrap();
+        recorder.record(step as f64 * 0.1, &registry).unwrap();
+    }
+    recorder.into_sink().unwrap()
+}
+
+#[test]
+fn test_arrow_sink_batches() {
+    let sink = run(ArrowSink::new().with_batch_size(4), 9);
+
+    let schema = sink.schema().unwrap();
+    let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(names, ["time", "habitat_1.temperature", "habitat_2.temperature", "habitat_3.temperature"]);
+    assert_eq!(schema.field(1).metadata()["unit"], "K");
+    assert_eq!(schema.field(1).metadata()["causality"], "output");
+
+    // 10 rows in batches of 4: 4 + 4 + 2
+    let rows: Vec<usize> = sink.batches().iter().map(|b| b.num_rows()).collect();
+    assert_eq!(rows, [4, 4, 2]);
+
+    let time = sink.batches()[2].column(0).as_any().downcast_ref::<Float64Array>().unwrap();
+    assert!((time.value(1) - 0.9).abs() < 1e-9);
+}
+
+#[test]
+fn test_parquet_sink_round_trip() {
+    let path = std::env::temp_dir().join(format!("parquet-sink-{}.parquet", std::process::id()));
+    let mut sink = run(ParquetSink::create(&path).unwrap().with_batch_size(16), 40);
+    sink.finish().unwrap();
+    drop(sink);
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap()).unwrap();
+    // Units and descriptions travel in the embedded Arrow schema
+    let field = reader.schema().field_with_name("habitat_2.temperature").unwrap().clone();
+    assert_eq!(field.metadata()["unit"], "K");
+
+    let batches: Vec<_> = reader.build().unwrap().map(|b| b.unwrap()).collect();
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 41);
+
+    let temperature = batches[0].column(1).as_any().downcast_ref::<Float64Array>().unwrap();
+    assert_eq!(temperature.value(0), 250.0);
+    assert!(temperature.value(temperature.len() - 1) > 250.0);
+
+    std::fs::remove_file(&path).unwrap();
+}
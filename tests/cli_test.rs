@@ -0,0 +1,121 @@
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+fn modelica_sim(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_modelica-sim"))
+        .args(args)
+        .output()
+        .expect("modelica-sim runs")
+}
+
+fn write_scenario(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("modelica-sim-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, r#"
+        step_size = 0.5
+        stop_time = 2.0
+
+        [[components]]
+        type = "SimpleThermalMVP"
+        name = "habitat_1"
+        initial = { heaterOn = true }
+    "#).unwrap();
+    path
+}
+
+#[test]
+fn test_cli_list_and_describe() {
+    let output = modelica_sim(&["list"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("SimpleThermalMVP"));
+
+    let output = modelica_sim(&["describe", "SimpleThermalMVP", "--json"]);
+    assert!(output.status.success());
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(metadata["outputs"][0]["name"], "temperature");
+
+    let output = modelica_sim(&["describe", "DoesNotExist"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("DoesNotExist"));
+}
+
+#[test]
+fn test_cli_run() {
+    let scenario = write_scenario("run");
+    let output = modelica_sim(&["run", scenario.to_str().unwrap()]);
+    assert!(output.status.success());
+
+    let csv = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "time [s],habitat_1.temperature [K],habitat_1.heaterStatus");
+    assert_eq!(lines.len(), 6);
+    assert!(lines[5].starts_with("2,"));
+
+    std::fs::remove_file(scenario).unwrap();
+}
+
+#[test]
+fn test_cli_run_overrides() {
+    let scenario = write_scenario("overrides");
+    let results = scenario.with_extension("csv");
+
+    let output = modelica_sim(&[
+        "run", scenario.to_str().unwrap(),
+        "--set", "habitat_1.roomCapacity=2",
+        "--stop-time", "1",
+        "-o", results.to_str().unwrap(),
+    ]);
+    assert!(output.status.success());
+
+    // 500 W into 2 J/K for 0.5 s
+    let csv = std::fs::read_to_string(&results).unwrap();
+    assert_eq!(csv.lines().nth(2).unwrap(), "0.5,375,1");
+
+    // Selection and output interval
+    let output = modelica_sim(&[
+        "run", scenario.to_str().unwrap(),
+        "--select", "*.temp*",
+        "--interval", "1",
+    ]);
+    assert!(output.status.success());
+    let csv = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "time [s],habitat_1.temperature [K]");
+    assert_eq!(lines.len(), 4);
+
+    let output = modelica_sim(&["run", scenario.to_str().unwrap(), "--set", "missing.heaterPower=1"]);
+    assert!(!output.status.success());
+
+    std::fs::remove_file(scenario).unwrap();
+    std::fs::remove_file(results).unwrap();
+}
+
+#[test]
+fn test_cli_compare() {
+    let scenario = write_scenario("compare");
+    let csv = scenario.with_extension("csv");
+    let mat = scenario.with_extension("mat");
+    let changed = std::env::temp_dir().join(format!("modelica-sim-compare-changed-{}.csv", std::process::id()));
+
+    for (path, extra) in [(&csv, None), (&mat, None), (&changed, Some("habitat_1.roomCapacity=2"))] {
+        let mut args = vec!["run", scenario.to_str().unwrap(), "-o", path.to_str().unwrap()];
+        if let Some(assignment) = extra {
+            args.extend(["--set", assignment]);
+        }
+        assert!(modelica_sim(&args).status.success());
+    }
+
+    // The same run as MAT and CSV agrees
+    let output = modelica_sim(&["compare", mat.to_str().unwrap(), csv.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("PASSED"));
+
+    let output = modelica_sim(&["compare", csv.to_str().unwrap(), changed.to_str().unwrap(), "--select", "*.temperature"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("habitat_1.temperature"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("out of tolerance"));
+
+    for path in [scenario, csv, mat, changed] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+use modelica_rust_ffi::compare::{compare, compare_matching, ResultSet, Tolerance};
+use modelica_rust_ffi::*;
+
+/// Runs a heated SimpleThermalMVP for 5 s into `sink`, one row per `dt`
+fn record<S: ResultSink>(sink: S, dt: f64) -> S {
+    let mut runtime = ModelicaRuntime::new("SimpleThermalMVP").unwrap();
+    runtime.initialize().unwrap();
+    runtime.set_bool_variable("heaterOn", true).unwrap();
+
+    let mut recorder = Recorder::new(sink).select("temperature").select("roomCapacity");
+    recorder.record(runtime.time(), &runtime).unwrap();
+    let steps = (5.0 / dt).round() as usize;
+    for _ in 0..steps {
+        runtime.step(dt).unwrap();
+        recorder.record(runtime.time(), &runtime).unwrap();
+    }
+    recorder.into_sink().unwrap()
+}
+
+fn csv_results(dt: f64) -> ResultSet {
+    let bytes = record(CsvSink::new(Vec::new()), dt).into_inner();
+    ResultSet::from_csv_str(&String::from_utf8(bytes).unwrap()).unwrap()
+}
+
+fn mat_results(dt: f64) -> ResultSet {
+    let bytes = record(MatSink::new(std::io::Cursor::new(Vec::new())), dt).into_inner().into_inner();
+    ResultSet::from_mat_bytes(&bytes).unwrap()
+}
+
+#[test]
+fn test_read_csv_and_mat() {
+    let csv = csv_results(0.5);
+    assert_eq!(csv.names().collect::<Vec<_>>(), ["temperature", "roomCapacity"]);
+    assert_eq!(csv.len(), 11);
+    assert_eq!(csv.values("temperature").unwrap()[0], 250.0);
+
+    // The fixed parameter comes back from data_1 as a constant series
+    let mat = mat_results(0.5);
+    assert_eq!(mat.time(), csv.time());
+    assert_eq!(mat.values("roomCapacity"), csv.values("roomCapacity"));
+    assert_eq!(mat.values("temperature"), csv.values("temperature"));
+
+    let comparison = compare(&mat, &csv, Tolerance::new(0.0, 0.0)).unwrap();
+    assert!(comparison.passed(), "{}", comparison);
+    assert_eq!(comparison.variable("temperature").unwrap().max_abs_error, 0.0);
+}
+
+#[test]
+fn test_interpolation() {
+    let mut results = ResultSet::new(vec![0.0, 1.0, 1.0, 3.0]).unwrap();
+    results.insert("x", vec![0.0, 2.0, 10.0, 14.0]).unwrap();
+
+    assert_eq!(results.value_at("x", 0.5), Some(1.0));
+    // After the event at t = 1
+    assert_eq!(results.value_at("x", 1.0), Some(10.0));
+    assert_eq!(results.value_at("x", 2.0), Some(12.0));
+    assert_eq!(results.value_at("x", 3.5), None);
+    assert_eq!(results.value_at("y", 0.5), None);
+
+    assert!(matches!(ResultSet::new(vec![0.0, 2.0, 1.0]), Err(ComponentError::InvalidResults(_))));
+}
+
+#[test]
+fn test_compare_different_grids() {
+    // A coarse run interpolated against a fine one stays close but not exact
+    let fine = csv_results(0.1);
+    let coarse = csv_results(0.5);
+
+    let comparison = compare(&fine, &coarse, Tolerance::new(0.5, 0.0)).unwrap();
+    assert!(comparison.passed(), "{}", comparison);
+    assert_eq!(comparison.start_time, 0.0);
+    assert!((comparison.stop_time - 5.0).abs() < 1e-9);
+    let temperature = comparison.variable("temperature").unwrap();
+    assert!(temperature.max_abs_error > 0.0);
+    assert!(temperature.max_rel_error < 1e-3);
+}
+
+#[test]
+fn test_compare_reports_first_violation() {
+    let reference = csv_results(0.5);
+    let mut actual = reference.clone();
+    let shifted: Vec<f64> = reference.time().iter().zip(reference.values("temperature").unwrap())
+        .map(|(&t, &v)| if t >= 2.0 { v + 0.5 } else { v })
+        .collect();
+    actual.insert("temperature", shifted).unwrap();
+
+    let comparison = compare(&reference, &actual, Tolerance::new(0.1, 0.0)).unwrap();
+    assert!(!comparison.passed());
+    let temperature = comparison.variable("temperature").unwrap();
+    // The step is interpolated over (1.5, 2.0]; the first grid point past 0.1 K is 2.0
+    assert_eq!(temperature.first_violation, Some(2.0));
+    assert!((temperature.max_abs_error - 0.5).abs() < 1e-12);
+    assert_eq!(comparison.failures().count(), 1);
+    assert!(comparison.to_string().contains("FAILED"));
+
+    // Relative tolerance alone accepts 0.5 K on ~250 K
+    assert!(compare(&reference, &actual, Tolerance::new(0.0, 1e-2)).unwrap().passed());
+}
+
+#[test]
+fn test_compare_errors() {
+    let reference = csv_results(0.5);
+
+    let mut later = ResultSet::new(vec![10.0, 11.0]).unwrap();
+    later.insert("temperature", vec![250.0, 251.0]).unwrap();
+    assert!(matches!(compare(&reference, &later, Tolerance::default()), Err(ComponentError::InvalidResults(_))));
+
+    assert!(matches!(
+        compare_matching(&reference, &reference, &["pump*"], Tolerance::default()),
+        Err(ComponentError::VariableNotFound(_))
+    ));
+
+    let mut partial = ResultSet::new(reference.time().to_vec()).unwrap();
+    partial.insert("temperature", reference.values("temperature").unwrap().to_vec()).unwrap();
+    let comparison = compare(&reference, &partial, Tolerance::default()).unwrap();
+    assert_eq!(comparison.missing, ["roomCapacity"]);
+    assert!(!comparison.passed());
+    assert!(compare_matching(&reference, &partial, &["temp*"], Tolerance::default()).unwrap().passed());
+
+    assert!(matches!(ResultSet::from_csv_str("time,x\n0,abc\n"), Err(ComponentError::InvalidResults(_))));
+    assert!(matches!(ResultSet::from_mat_bytes(&[1, 2, 3]), Err(ComponentError::InvalidResults(_))));
+}
+
+#[test]
+fn test_compare_time_range_mismatch() {
+    let reference = csv_results(0.5);
+
+    // Truncated: every shared point matches, but the run stops at 2.5 s
+    let samples = reference.time().iter().filter(|&&t| t <= 2.5).count();
+    let mut truncated = ResultSet::new(reference.time()[..samples].to_vec()).unwrap();
+    for name in reference.names() {
+        truncated.insert(name, reference.values(name).unwrap()[..samples].to_vec()).unwrap();
+    }
+    let comparison = compare(&reference, &truncated, Tolerance::new(0.0, 0.0)).unwrap();
+    assert_eq!(comparison.failures().count(), 0);
+    assert_eq!(comparison.actual_range, (0.0, 2.5));
+    assert!(!comparison.time_range_matches());
+    assert!(!comparison.passed());
+    assert!(comparison.to_string().contains("Time range [0, 2.5] s differs from the reference [0, 5] s"));
+
+    // Extended runs fail the other way round
+    let comparison = compare(&truncated, &reference, Tolerance::new(0.0, 0.0)).unwrap();
+    assert!(!comparison.passed());
+}
+
+#[test]
+fn test_mat_header_overflow() {
+    // "x": a 2^31-1 by 2^31-1 f64 matrix with no data
+    let mut bytes = Vec::new();
+    for field in [0, i32::MAX, i32::MAX, 0, 2] {
+        bytes.extend_from_slice(&i32::to_le_bytes(field));
+    }
+    bytes.extend_from_slice(b"x\0");
+    assert!(matches!(ResultSet::from_mat_bytes(&bytes), Err(ComponentError::InvalidResults(_))));
+}
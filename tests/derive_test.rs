@@ -0,0 +1,200 @@
+use modelica_rust_ffi::*;
+
+/// On/off thermostat driving a first-order room model
+#[derive(SimulationComponent)]
+#[component(name = "Thermostat", category = "Control", update = update_outputs)]
+struct Thermostat {
+    /// Heat capacity of the room
+    #[parameter(unit = "J/K", min = 1.0, start = 1000.0)]
+    capacity: f64,
+
+    /// Temperature the heater switches off at
+    #[parameter(name = "setPoint", unit = "K")]
+    set_point: f64,
+
+    /// Manual override
+    #[input(name = "enabled", start = true)]
+    enabled: bool,
+
+    /// Room temperature
+    #[state(unit = "K", min = 0.0, start = 250.0, der = temperature_derivative)]
+    temperature: f64,
+
+    #[output(name = "heaterOn")]
+    heater_on: bool,
+
+    #[output]
+    cycles: u32,
+
+    // Not part of the variable table
+    history: Vec<f64>,
+}
+
+impl Thermostat {
+    fn new(set_point: f64) -> Self {
+        Self {
+            capacity: 1000.0,
+            set_point,
+            enabled: true,
+            temperature: 250.0,
+            heater_on: false,
+            cycles: 0,
+            history: Vec::new(),
+        }
+    }
+
+    fn temperature_derivative(&self) -> f64 {
+        if self.heater_on { 500.0 / self.capacity } else { 0.0 }
+    }
+
+    fn update_outputs(&mut self) {
+        let on = self.enabled && self.temperature < self.set_point;
+        if on && !self.heater_on {
+            self.cycles += 1;
+        }
+        self.heater_on = on;
+        self.history.push(self.temperature);
+    }
+}
+
+#[test]
+fn test_derive_metadata() {
+    let thermostat = Thermostat::new(260.0);
+    assert_eq!(thermostat.component_type(), "Thermostat");
+
+    let metadata = thermostat.metadata();
+    assert_eq!(metadata.component_type, "Control");
+    assert_eq!(metadata.inputs.len(), 1);
+    assert_eq!(metadata.outputs.len(), 2);
+    assert_eq!(metadata.parameters.len(), 2);
+    assert_eq!(metadata.locals.len(), 1);
+
+    let capacity = metadata.find("capacity").unwrap();
+    assert_eq!(capacity.unit.as_deref(), Some("J/K"));
+    assert_eq!(capacity.description.as_deref(), Some("Heat capacity of the room"));
+    assert_eq!(capacity.start, Some(1000.0));
+    assert_eq!(capacity.min, Some(1.0));
+
+    assert_eq!(metadata.find("enabled").unwrap().io_type, IOType::Boolean);
+    assert_eq!(metadata.find("cycles").unwrap().io_type, IOType::Integer);
+    assert_eq!(metadata.find("temperature").unwrap().causality, Causality::Local);
+    assert!(metadata.find("history").is_none());
+}
+
+#[test]
+fn test_derive_step() {
+    let mut thermostat = Thermostat::new(260.0);
+    thermostat.initialize().unwrap();
+    assert_eq!(thermostat.get_output("heaterOn").unwrap(), 1.0);
+
+    for _ in 0..30 {
+        thermostat.step(1.0).unwrap();
+    }
+
+    // 0.5 K/s until the set point is reached
+    let temperature = thermostat.get_output("temperature").unwrap();
+    assert!((260.0..261.0).contains(&temperature));
+    assert_eq!(thermostat.get_output("heaterOn").unwrap(), 0.0);
+    assert_eq!(thermostat.get_output("cycles").unwrap(), 1.0);
+
+    let outputs = thermostat.get_all_outputs();
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(outputs["cycles"], 1.0);
+
+    assert!(matches!(thermostat.step(0.0), Err(ComponentError::StepFailed(_))));
+}
+
+#[test]
+fn test_derive_set_and_reset() {
+    let mut thermostat = Thermostat::new(260.0);
+    thermostat.initialize().unwrap();
+
+    thermostat.set_input("setPoint", 255.0).unwrap();
+    thermostat.set_parameter("capacity", 2000.0).unwrap();
+    thermostat.set_input("temperature", 270.0).unwrap();
+    thermostat.set_bool_input("enabled", false).unwrap();
+    assert_eq!(thermostat.get_output("setPoint").unwrap(), 255.0);
+
+    // Type, causality, bounds and name checks
+    assert!(matches!(thermostat.set_input("enabled", 1.0), Err(ComponentError::InvalidInput(_))));
+    assert!(matches!(thermostat.set_bool_input("capacity", true), Err(ComponentError::InvalidInput(_))));
+    assert!(matches!(thermostat.set_input("heaterOn", 1.0), Err(ComponentError::InvalidInput(_))));
+    assert!(matches!(thermostat.set_parameter("capacity", 0.0), Err(ComponentError::BoundsCheckFailed(..))));
+    assert!(matches!(thermostat.set_input("temperature", f64::NAN), Err(ComponentError::InvalidInput(_))));
+    assert!(matches!(thermostat.set_parameter("temperature", 1.0), Err(ComponentError::VariableNotFound(_))));
+    assert!(matches!(thermostat.get_output("history"), Err(ComponentError::VariableNotFound(_))));
+
+    // Start values come back; parameters keep their value
+    thermostat.reset().unwrap();
+    assert_eq!(thermostat.get_output("capacity").unwrap(), 2000.0);
+    assert_eq!(thermostat.get_output("temperature").unwrap(), 250.0);
+    assert_eq!(thermostat.get_output("enabled").unwrap(), 1.0);
+    assert_eq!(thermostat.get_output("setPoint").unwrap(), 255.0);
+}
+
+#[test]
+fn test_derive_in_registry() {
+    let mut registry = ComponentRegistry::new();
+    let id = registry.add("thermostat".to_string(), Box::new(Thermostat::new(260.0))).unwrap();
+    registry.get_mut(id).unwrap().initialize().unwrap();
+    registry.step_all(1.0).unwrap();
+
+    let temperature = registry.get_by_name("thermostat").unwrap().get_output("temperature").unwrap();
+    assert_eq!(temperature, 250.5);
+}
+
+/// Tank with a fixed size and integer-valued settings
+#[derive(SimulationComponent)]
+#[component(initialized = initialized)]
+struct Tank {
+    #[parameter(fixed, unit = "m3", start = 10.0)]
+    volume: f64,
+
+    #[parameter(start = 2)]
+    pumps: u8,
+
+    #[input]
+    offset: i32,
+
+    #[state(der = fill_rate)]
+    level: f64,
+
+    initialized: bool,
+}
+
+impl Tank {
+    fn fill_rate(&self) -> f64 {
+        self.pumps as f64 / self.volume
+    }
+}
+
+#[test]
+fn test_derive_integers_and_fixed_parameters() {
+    let mut tank = Tank { volume: 10.0, pumps: 2, offset: 0, level: 0.0, initialized: false };
+    let metadata = tank.metadata();
+    assert_eq!(metadata.find("volume").unwrap().variability, Variability::Fixed);
+    assert_eq!(metadata.find("pumps").unwrap().variability, Variability::Tunable);
+
+    // Whole, in-range values only
+    tank.set_parameter("pumps", 3.0).unwrap();
+    tank.set_input("offset", -3.0).unwrap();
+    assert!(matches!(tank.set_parameter("pumps", 1.7), Err(ComponentError::InvalidInput(_))));
+    assert!(matches!(tank.set_parameter("pumps", -3.0), Err(ComponentError::InvalidInput(_))));
+    assert!(matches!(tank.set_parameter("pumps", 256.0), Err(ComponentError::InvalidInput(_))));
+    assert!(matches!(tank.set_input("offset", 2147483648.0), Err(ComponentError::InvalidInput(_))));
+    assert_eq!(tank.get_output("pumps").unwrap(), 3.0);
+    assert_eq!(tank.get_output("offset").unwrap(), -3.0);
+
+    // Fixed parameters freeze on initialize or the first step, until a reset
+    tank.set_parameter("volume", 20.0).unwrap();
+    tank.step(1.0).unwrap();
+    assert!(matches!(tank.set_parameter("volume", 5.0), Err(ComponentError::FixedParameter(_))));
+    assert!(matches!(tank.set_input("volume", 5.0), Err(ComponentError::FixedParameter(_))));
+    tank.set_parameter("pumps", 1.0).unwrap();
+
+    tank.reset().unwrap();
+    tank.set_parameter("volume", 5.0).unwrap();
+    tank.initialize().unwrap();
+    assert_eq!(tank.get_output("volume").unwrap(), 5.0);
+    assert!(matches!(tank.set_parameter("volume", 6.0), Err(ComponentError::FixedParameter(_))));
+}
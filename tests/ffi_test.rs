@@ -0,0 +1,113 @@
+use modelica_rust_ffi::ffi::*;
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+fn last_error() -> String {
+    unsafe { CStr::from_ptr(mrf_last_error()).to_string_lossy().into_owned() }
+}
+
+#[test]
+fn test_ffi_registry_lifecycle() {
+    unsafe {
+        let registry = mrf_registry_new();
+        assert!(!registry.is_null());
+
+        let status = mrf_registry_add(registry, c("SimpleThermalMVP").as_ptr(), c("habitat").as_ptr());
+        assert_eq!(status, MrfStatus::Ok);
+        assert_eq!(mrf_registry_len(registry), 1);
+
+        let status = mrf_component_set_bool(registry, c("habitat").as_ptr(), c("heaterOn").as_ptr(), true);
+        assert_eq!(status, MrfStatus::Ok);
+        assert_eq!(mrf_component_set_parameter(registry, c("habitat").as_ptr(), c("heaterPower").as_ptr(), 750.0), MrfStatus::Ok);
+
+        for _ in 0..10 {
+            assert_eq!(mrf_registry_step_all(registry, 0.1), MrfStatus::Ok);
+        }
+
+        let mut temperature = 0.0;
+        let status = mrf_component_get_real(registry, c("habitat").as_ptr(), c("temperature").as_ptr(), &mut temperature);
+        assert_eq!(status, MrfStatus::Ok);
+        assert!(temperature > 250.0);
+
+        assert_eq!(mrf_component_reset(registry, c("habitat").as_ptr()), MrfStatus::Ok);
+        assert_eq!(mrf_registry_remove(registry, c("habitat").as_ptr()), MrfStatus::Ok);
+        assert_eq!(mrf_registry_len(registry), 0);
+
+        mrf_registry_free(registry);
+    }
+}
+
+#[test]
+fn test_ffi_add_with_parameters() {
+    unsafe {
+        let registry = mrf_registry_new();
+
+        // Fixed parameters can only be set at creation
+        let names = [c("roomCapacity")];
+        let name_ptrs = [names[0].as_ptr()];
+        let values = [2000.0];
+        let status = mrf_registry_add_with_parameters(
+            registry,
+            c("SimpleThermalMVP").as_ptr(),
+            c("habitat").as_ptr(),
+            name_ptrs.as_ptr(),
+            values.as_ptr(),
+            1,
+        );
+        assert_eq!(status, MrfStatus::Ok);
+
+        let mut capacity = 0.0;
+        mrf_component_get_real(registry, c("habitat").as_ptr(), c("roomCapacity").as_ptr(), &mut capacity);
+        assert_eq!(capacity, 2000.0);
+
+        let status = mrf_component_set_parameter(registry, c("habitat").as_ptr(), c("roomCapacity").as_ptr(), 10.0);
+        assert_eq!(status, MrfStatus::InvalidInput);
+
+        mrf_registry_free(registry);
+    }
+}
+
+#[test]
+fn test_ffi_errors() {
+    unsafe {
+        let registry = mrf_registry_new();
+
+        let status = mrf_registry_add(registry, c("DoesNotExist").as_ptr(), c("x").as_ptr());
+        assert_eq!(status, MrfStatus::NotFound);
+        assert!(last_error().contains("DoesNotExist"));
+
+        mrf_registry_add(registry, c("SimpleThermalMVP").as_ptr(), c("habitat").as_ptr());
+        let status = mrf_registry_add(registry, c("SimpleThermalMVP").as_ptr(), c("habitat").as_ptr());
+        assert_eq!(status, MrfStatus::Error);
+
+        let status = mrf_component_set_real(registry, c("missing").as_ptr(), c("heaterOn").as_ptr(), 1.0);
+        assert_eq!(status, MrfStatus::NotFound);
+        assert!(last_error().contains("missing"));
+
+        let status = mrf_component_set_real(registry, c("habitat").as_ptr(), c("temperature").as_ptr(), 1.0);
+        assert_eq!(status, MrfStatus::InvalidInput);
+
+        assert_eq!(mrf_registry_step_all(registry, -1.0), MrfStatus::StepFailed);
+
+        let status = mrf_component_get_real(registry, c("habitat").as_ptr(), c("temperature").as_ptr(), ptr::null_mut());
+        assert_eq!(status, MrfStatus::NullPointer);
+        assert_eq!(mrf_registry_step_all(ptr::null_mut(), 0.1), MrfStatus::NullPointer);
+        assert_eq!(mrf_registry_len(ptr::null()), 0);
+
+        let invalid = [0xffu8, 0];
+        let status = mrf_registry_remove(registry, invalid.as_ptr().cast());
+        assert_eq!(status, MrfStatus::InvalidString);
+
+        mrf_registry_free(registry);
+    }
+}
+
+#[test]
+fn test_ffi_version() {
+    let version = unsafe { CStr::from_ptr(mrf_version()) };
+    assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+}
@@ -0,0 +1,77 @@
+use modelica_rust_ffi::compare::Tolerance;
+use modelica_rust_ffi::golden::GoldenTest;
+use modelica_rust_ffi::*;
+
+// Regenerate with `BLESS=1 cargo test --test golden_test` and review the CSV diff
+#[test]
+fn test_golden_habitat_heating() {
+    GoldenTest::new("tests/golden/habitat_heating.toml", "tests/golden/habitat_heating.csv")
+        .unwrap()
+        .with_interval(2.0)
+        .assert();
+}
+
+fn scenario(heater_power: f64) -> Scenario {
+    Scenario::from_toml_str(&format!(r#"
+        step_size = 0.5
+        stop_time = 10.0
+
+        [[components]]
+        type = "SimpleThermalMVP"
+        name = "habitat_1"
+        parameters = {{ heaterPower = {}, roomCapacity = 100.0 }}
+        initial = {{ heaterOn = true }}
+    "#, heater_power)).unwrap()
+}
+
+#[test]
+fn test_golden_bless_and_drift() {
+    let golden = std::env::temp_dir()
+        .join(format!("golden-{}", std::process::id()))
+        .join("heating.csv");
+
+    // A missing golden file is an error until blessed
+    assert!(GoldenTest::from_scenario(scenario(500.0), &golden).bless(false).verify().is_err());
+
+    let report = GoldenTest::from_scenario(scenario(500.0), &golden).bless(true).verify().unwrap();
+    assert!(report.blessed && report.passed());
+    assert!(std::fs::read_to_string(&golden).unwrap().starts_with("time [s],habitat_1.temperature [K]"));
+
+    let report = GoldenTest::from_scenario(scenario(500.0), &golden).bless(false).verify().unwrap();
+    assert!(report.passed(), "{}", report);
+
+    // 10 W more heats 100 J/K by 0.05 K per second
+    let report = GoldenTest::from_scenario(scenario(510.0), &golden).bless(false).verify().unwrap();
+    assert!(!report.passed());
+    assert_eq!(report.comparison.failures().next().unwrap().name, "habitat_1.temperature");
+    let text = report.to_string();
+    assert!(text.contains("drifted from"));
+    assert!(text.contains("BLESS=1"));
+
+    // Loose enough tolerance accepts the drift
+    let report = GoldenTest::from_scenario(scenario(510.0), &golden)
+        .with_tolerance(Tolerance::new(1.0, 0.0))
+        .bless(false)
+        .verify()
+        .unwrap();
+    assert!(report.passed());
+
+    // A run cut short fails even though the shared range matches
+    let mut truncated = scenario(500.0);
+    truncated.stop_time = 5.0;
+    let report = GoldenTest::from_scenario(truncated, &golden).bless(false).verify().unwrap();
+    assert!(report.comparison.failures().next().is_none());
+    assert!(!report.comparison.time_range_matches());
+    assert!(!report.passed());
+
+    // New recorded variables need blessing too
+    let report = GoldenTest::from_scenario(scenario(500.0), &golden)
+        .select("*")
+        .bless(false)
+        .verify()
+        .unwrap();
+    assert!(report.extra.contains(&"habitat_1.heaterPower".to_string()));
+    assert!(!report.passed());
+
+    std::fs::remove_dir_all(golden.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "c-header")]
+//! Keeps the committed C header in sync with src/ffi.rs
+//!
+//! ```text
+//! cargo test --features c-header --test header_test            # check
+//! BLESS=1 cargo test --features c-header --test header_test    # update include/
+//! ```
+
+use modelica_rust_ffi::golden::BLESS_VAR;
+use std::path::Path;
+
+/// Written by build.rs
+const GENERATED: &str = include_str!(concat!(env!("OUT_DIR"), "/modelica_rust_ffi.h"));
+
+#[test]
+fn test_committed_header_is_current() {
+    let committed_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/modelica_rust_ffi.h");
+    let bless = std::env::var(BLESS_VAR).is_ok_and(|value| !value.is_empty() && value != "0");
+    if bless {
+        std::fs::write(&committed_path, GENERATED).unwrap();
+    }
+
+    let committed = std::fs::read_to_string(&committed_path).unwrap();
+    assert!(
+        committed == GENERATED,
+        "{} is out of date with src/ffi.rs; rerun with {}=1 to regenerate it",
+        committed_path.display(), BLESS_VAR
+    );
+}
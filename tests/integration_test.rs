@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use modelica_rust_ffi::*;
 
 #[test]
@@ -196,4 +198,255 @@ fn test_debug_trait() {
     let debug = format!("{:?}", runtime);
     assert!(debug.contains("ModelicaRuntime"));
     assert!(debug.contains("component_name"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_with_parameters() {
+    let mut parameters = std::collections::HashMap::new();
+    parameters.insert("ambientTemp".to_string(), 280.0);
+    parameters.insert("heaterPower".to_string(), 750.0);
+    
+    let runtime = ModelicaRuntime::with_parameters("SimpleThermalMVP", parameters).unwrap();
+    assert_eq!(runtime.get_parameter("heaterPower").unwrap(), 750.0);
+    
+    // Start values follow the overridden ambient temperature
+    assert_eq!(runtime.get_real_variable("temperature").unwrap(), 280.0);
+}
+
+#[test]
+fn test_with_parameters_unknown() {
+    let mut parameters = std::collections::HashMap::new();
+    parameters.insert("roomTemp".to_string(), 280.0);
+    
+    // States are not parameters
+    let result = ModelicaRuntime::with_parameters("SimpleThermalMVP", parameters);
+    assert!(matches!(result.unwrap_err(), ComponentError::VariableNotFound(_)));
+}
+
+#[test]
+fn test_fixed_parameter_enforcement() {
+    let mut runtime = ModelicaRuntime::new("SimpleThermalMVP").unwrap();
+    assert!(!runtime.is_initialized());
+    
+    // Anything goes before initialization
+    runtime.set_parameter("roomCapacity", 2000.0).unwrap();
+    runtime.initialize().unwrap();
+    assert!(runtime.is_initialized());
+    
+    // Fixed parameters are locked, tunable ones are not
+    let result = runtime.set_parameter("roomCapacity", 3000.0);
+    assert!(matches!(result.unwrap_err(), ComponentError::FixedParameter(_)));
+    let result = runtime.set_real_variable("ambientTemp", 260.0);
+    assert!(matches!(result.unwrap_err(), ComponentError::FixedParameter(_)));
+    runtime.set_parameter("heaterPower", 900.0).unwrap();
+    
+    // A reset starts a new run
+    runtime.reset().unwrap();
+    runtime.set_parameter("roomCapacity", 3000.0).unwrap();
+    assert_eq!(runtime.get_parameter("roomCapacity").unwrap(), 3000.0);
+}
+
+#[test]
+fn test_step_freezes_fixed_parameters() {
+    let mut runtime = ModelicaRuntime::new("SimpleThermalMVP").unwrap();
+    runtime.step(0.1).unwrap();
+    assert!(runtime.is_initialized());
+    assert!(runtime.set_parameter("ambientTemp", 300.0).is_err());
+}
+
+#[test]
+fn test_parameter_metadata() {
+    let component = SimpleThermalComponent::new().unwrap();
+    let metadata = component.metadata();
+    
+    let capacity = metadata.parameters.iter().find(|p| p.name == "roomCapacity").unwrap();
+    assert_eq!(capacity.start, Some(1000.0));
+    assert_eq!(capacity.variability, Variability::Fixed);
+    
+    let power = metadata.parameters.iter().find(|p| p.name == "heaterPower").unwrap();
+    assert_eq!(power.variability, Variability::Tunable);
+    assert_eq!(power.unit.as_deref(), Some("W"));
+}
+
+#[test]
+fn test_variable_metadata() {
+    let component = SimpleThermalComponent::new().unwrap();
+    let metadata = component.metadata();
+    
+    let names: Vec<&str> = metadata.inputs.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, vec!["heaterOn"]);
+    let names: Vec<&str> = metadata.outputs.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, vec!["temperature", "heaterStatus"]);
+    
+    let temperature = metadata.find("temperature").unwrap();
+    assert_eq!(temperature.causality, Causality::Output);
+    assert_eq!(temperature.variability, Variability::Continuous);
+    assert_eq!(temperature.display_unit.as_deref(), Some("degC"));
+    assert_eq!((temperature.min, temperature.max), (Some(0.0), Some(1000.0)));
+    
+    let state = metadata.find("roomTemp").unwrap();
+    assert_eq!(state.causality, Causality::Local);
+    assert!(metadata.find("time").is_none());
+}
+
+#[test]
+fn test_runtime_variable_table() {
+    let mut runtime = ModelicaRuntime::new("SimpleThermalMVP").unwrap();
+    
+    let time = runtime.variable("time").unwrap();
+    assert_eq!(time.causality, Causality::Independent);
+    runtime.step(0.5).unwrap();
+    assert_eq!(runtime.get_real_variable("time").unwrap(), 0.5);
+    
+    // Bounds come from min/max in the variable table
+    let result = runtime.set_real_variable("heaterStatus", 2.0);
+    assert!(matches!(result.unwrap_err(), ComponentError::BoundsCheckFailed(_, _, _, _)));
+    runtime.reset().unwrap();
+    let result = runtime.set_parameter("roomCapacity", -1.0);
+    assert!(matches!(result.unwrap_err(), ComponentError::BoundsCheckFailed(_, _, _, _)));
+}
+
+#[test]
+fn test_generic_component() {
+    let mut component = ModelicaComponent::new("SimpleThermalMVP").unwrap();
+    component.initialize().unwrap();
+    assert_eq!(component.component_type(), "SimpleThermalMVP");
+    assert_eq!(component.metadata().component_type, "Thermal");
+    
+    // Outputs come from the variable table
+    let outputs = component.get_all_outputs();
+    let mut names: Vec<&String> = outputs.keys().collect();
+    names.sort();
+    assert_eq!(names, vec!["heaterStatus", "temperature"]);
+    
+    // Boolean variables read as 0/1
+    component.set_bool_input("heaterOn", true).unwrap();
+    assert_eq!(component.get_output("heaterOn").unwrap(), 1.0);
+}
+
+#[test]
+fn test_generic_component_input_validation() {
+    let mut component = ModelicaComponent::new("SimpleThermalMVP").unwrap();
+    component.initialize().unwrap();
+    
+    // Unknown variable
+    let result = component.set_input("nonexistent", 1.0);
+    assert!(matches!(result.unwrap_err(), ComponentError::VariableNotFound(_)));
+    
+    // Outputs are computed by the model
+    let result = component.set_input("temperature", 300.0);
+    assert!(matches!(result.unwrap_err(), ComponentError::InvalidInput(_)));
+    
+    // Type mismatch
+    let result = component.set_input("heaterOn", 1.0);
+    assert!(matches!(result.unwrap_err(), ComponentError::InvalidInput(_)));
+    let result = component.set_bool_input("roomTemp", true);
+    assert!(matches!(result.unwrap_err(), ComponentError::InvalidInput(_)));
+    
+    // Parameters follow fixed/tunable rules
+    component.set_input("heaterPower", 800.0).unwrap();
+    let result = component.set_input("roomCapacity", 800.0);
+    assert!(matches!(result.unwrap_err(), ComponentError::FixedParameter(_)));
+    
+    // States can be set, e.g. as initial conditions, within their bounds
+    component.set_input("roomTemp", 300.0).unwrap();
+    assert_eq!(component.get_output("roomTemp").unwrap(), 300.0);
+    let result = component.set_input("roomTemp", 2000.0);
+    assert!(matches!(result.unwrap_err(), ComponentError::BoundsCheckFailed(..)));
+    
+    // Integers must be whole
+    let count = IOSpec::new("count", IOType::Integer, Causality::Input, Variability::Discrete).with_min(0.0);
+    count.check_value(3.0).unwrap();
+    assert!(matches!(count.check_value(2.5), Err(ComponentError::InvalidInput(_))));
+    assert!(matches!(count.check_value(-1.0), Err(ComponentError::BoundsCheckFailed(..))));
+}
+
+#[test]
+fn test_typed_model() {
+    use modelica_rust_ffi::models::SimpleThermalMVP;
+
+    let mut thermal = SimpleThermalMVP::new().unwrap();
+    thermal.initialize().unwrap();
+    assert_eq!(thermal.room_capacity(), 1000.0);
+    assert_eq!(SimpleThermalMVP::TEMPERATURE.name, "temperature");
+    assert_eq!(SimpleThermalMVP::TEMPERATURE.unit, Some("K"));
+
+    thermal.set_heater_power(750.0).unwrap();
+    thermal.set_heater_on(true).unwrap();
+    assert!(thermal.heater_on());
+    thermal.step(1.0).unwrap();
+
+    assert!(thermal.temperature() > 250.0);
+    assert_eq!(thermal.heater_status(), 1.0);
+    assert_eq!(thermal.time(), 1.0);
+
+    // Fixed parameters are still enforced through the typed setter
+    assert!(matches!(thermal.set_room_capacity(2000.0), Err(ComponentError::FixedParameter(_))));
+
+    // Usable wherever a component is expected
+    let mut registry = ComponentRegistry::new();
+    registry.add("habitat".to_string(), Box::new(thermal)).unwrap();
+}
+
+#[test]
+fn test_registry_initialize_and_reset_all() {
+    let mut registry = ComponentRegistry::new();
+    registry.add("habitat".to_string(), Box::new(SimpleThermalComponent::new().unwrap())).unwrap();
+    registry.initialize_all().unwrap();
+
+    registry.get_mut_by_name("habitat").unwrap().set_bool_input("heaterOn", true).unwrap();
+    registry.step_all(1.0).unwrap();
+    assert_eq!(registry.time(), 1.0);
+    assert!(registry.get_by_name("habitat").unwrap().get_output("temperature").unwrap() > 250.0);
+
+    registry.reset_all().unwrap();
+    assert_eq!(registry.time(), 0.0);
+    assert_eq!(registry.get_by_name("habitat").unwrap().get_output("temperature").unwrap(), 250.0);
+}
+
+#[test]
+fn test_runtime_messages() {
+    let mut runtime = ModelicaRuntime::new("SimpleThermalMVP").unwrap();
+    runtime.initialize().unwrap();
+    runtime.step(1.0).unwrap();
+
+    // The Rust models report nothing; OpenModelica warnings land here
+    assert!(runtime.messages().is_empty());
+}
+
+#[test]
+fn test_failed_step_is_recoverable() {
+    let mut runtime = ModelicaRuntime::new("SimpleThermalMVP").unwrap();
+    runtime.initialize().unwrap();
+    runtime.set_bool_variable("heaterOn", true).unwrap();
+    runtime.step(1.0).unwrap();
+    let temperature = runtime.get_real_variable("temperature").unwrap();
+
+    // Drives the temperature past its max of 1000 K
+    runtime.set_parameter("heaterPower", 1e9).unwrap();
+    assert!(runtime.step(1.0).is_err());
+    assert_eq!(runtime.get_real_variable("temperature").unwrap(), temperature);
+    assert_eq!(runtime.get_real_variable("roomTemp").unwrap(), temperature);
+    assert_eq!(runtime.time(), 1.0);
+
+    runtime.set_parameter("heaterPower", 500.0).unwrap();
+    runtime.step(1.0).unwrap();
+    assert!(runtime.get_real_variable("temperature").unwrap() > temperature);
+    assert_eq!(runtime.time(), 2.0);
+}
+
+#[test]
+fn test_type_metadata() {
+    let metadata = SimpleThermalComponent::type_metadata().unwrap();
+    let instance = SimpleThermalComponent::new().unwrap().metadata();
+    assert_eq!(metadata.parameters.len(), 4);
+    assert_eq!(
+        serde_json::to_value(&metadata).unwrap(),
+        serde_json::to_value(&instance).unwrap()
+    );
+
+    assert!(matches!(
+        ModelicaComponent::type_metadata("NoSuchModel"),
+        Err(ComponentError::UnknownComponentType(_))
+    ));
+}
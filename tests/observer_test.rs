@@ -0,0 +1,147 @@
+use modelica_rust_ffi::*;
+use std::sync::{Arc, Mutex};
+
+fn habitat() -> ComponentRegistry {
+    let mut registry = ComponentRegistry::new();
+    let mut thermal = SimpleThermalComponent::with_parameters(
+        &[("roomCapacity".to_string(), 100.0)].into_iter().collect(),
+    ).unwrap();
+    thermal.initialize().unwrap();
+    registry.add("habitat_1".to_string(), Box::new(thermal)).unwrap();
+    registry
+}
+
+#[test]
+fn test_step_callbacks() {
+    let mut runtime = ModelicaRuntime::new("SimpleThermalMVP").unwrap();
+    runtime.initialize().unwrap();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let before = log.clone();
+    runtime.on_before_step(move |_, step| before.lock().unwrap().push(format!("before {} {}", step.time, step.dt)));
+    let after = log.clone();
+    let id = runtime.on_after_step(move |runtime, step| {
+        after.lock().unwrap().push(format!("after {} {}", step.time, runtime.time()));
+    });
+
+    runtime.step(0.5).unwrap();
+    runtime.step(0.5).unwrap();
+    assert_eq!(*log.lock().unwrap(), ["before 0 0.5", "after 0.5 0.5", "before 0.5 0.5", "after 1 1"]);
+
+    assert!(runtime.remove_observer(id));
+    assert!(!runtime.remove_observer(id));
+    runtime.step(0.5).unwrap();
+    assert_eq!(log.lock().unwrap().last().unwrap(), "before 1 0.5");
+}
+
+#[test]
+fn test_event_callback() {
+    let mut registry = habitat();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    registry.on_event(move |_, event| sink.lock().unwrap().push(event.clone()));
+
+    registry.step_all(1.0).unwrap();
+    assert!(events.lock().unwrap().is_empty());
+
+    // The heater status follows the input on the next step
+    registry.get_mut_by_name("habitat_1").unwrap().set_bool_input("heaterOn", true).unwrap();
+    registry.step_all(1.0).unwrap();
+    registry.step_all(1.0).unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].variable, "habitat_1.heaterStatus");
+    assert_eq!((events[0].previous, events[0].value), (0.0, 1.0));
+    assert_eq!(events[0].time, 2.0);
+}
+
+#[test]
+fn test_threshold_callback() {
+    let mut registry = habitat();
+    let crossings = Arc::new(Mutex::new(Vec::new()));
+    let sink = crossings.clone();
+    registry.on_threshold("habitat_1.temperature", 260.0, move |_, crossing| {
+        sink.lock().unwrap().push((crossing.time, crossing.direction));
+    }).unwrap();
+
+    assert!(matches!(
+        registry.on_threshold("habitat_1.pressure", 1.0, |_, _| {}),
+        Err(ComponentError::VariableNotFound(_))
+    ));
+
+    // 500 W into 100 J/K: ~5 K/s up, then losses bring it back down
+    registry.get_mut_by_name("habitat_1").unwrap().set_bool_input("heaterOn", true).unwrap();
+    for _ in 0..5 {
+        registry.step_all(1.0).unwrap();
+    }
+    registry.get_mut_by_name("habitat_1").unwrap().set_bool_input("heaterOn", false).unwrap();
+    for _ in 0..200 {
+        registry.step_all(1.0).unwrap();
+    }
+
+    let crossings = crossings.lock().unwrap();
+    assert_eq!(crossings.len(), 2, "{:?}", crossings);
+    assert_eq!(crossings[0].1, Direction::Rising);
+    assert!(crossings[0].0 <= 5.0);
+    assert_eq!(crossings[1].1, Direction::Falling);
+    assert!(crossings[1].0 > 5.0);
+}
+
+#[test]
+fn test_error_callback() {
+    let mut registry = habitat();
+    let failures = Arc::new(Mutex::new(Vec::new()));
+    let sink = failures.clone();
+    registry.on_error(move |failure| {
+        sink.lock().unwrap().push((failure.time, failure.component.map(str::to_string), failure.error.to_string()));
+    });
+
+    registry.step_all(1.0).unwrap();
+    assert!(registry.step_all(-1.0).is_err());
+    assert_eq!(registry.time(), 1.0);
+
+    let failures = failures.lock().unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, 1.0);
+    assert_eq!(failures[0].1.as_deref(), Some("habitat_1"));
+    assert!(failures[0].2.contains("Invalid timestep"));
+}
+
+#[test]
+fn test_panicking_callback_keeps_observers() {
+    let mut registry = habitat();
+    let steps = Arc::new(Mutex::new(0));
+    let counter = steps.clone();
+    registry.on_after_step(move |_, _| *counter.lock().unwrap() += 1);
+    let panicking = registry.on_after_step(|_, step| {
+        if step.time == 1.0 {
+            panic!("observer failed");
+        }
+    });
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| registry.step_all(1.0)));
+    assert!(result.is_err());
+    assert_eq!(*steps.lock().unwrap(), 1);
+
+    // Both callbacks are still registered
+    registry.step_all(1.0).unwrap();
+    assert_eq!(*steps.lock().unwrap(), 2);
+    assert!(registry.remove_observer(panicking));
+}
+
+#[test]
+fn test_clear_keeps_ids_unique() {
+    let mut registry = habitat();
+    let stale = registry.on_after_step(|_, _| {});
+    registry.observers().clear();
+
+    let steps = Arc::new(Mutex::new(0));
+    let counter = steps.clone();
+    let id = registry.on_after_step(move |_, _| *counter.lock().unwrap() += 1);
+    assert_ne!(id, stale);
+    assert!(!registry.remove_observer(stale));
+
+    registry.step_all(1.0).unwrap();
+    assert_eq!(*steps.lock().unwrap(), 1);
+}
@@ -0,0 +1,62 @@
+# Run with: maturin develop && pytest tests/python
+import numpy as np
+import pytest
+
+import modelica_rust_ffi as mrf
+
+
+def test_runtime():
+    runtime = mrf.ModelicaRuntime("SimpleThermalMVP", {"roomCapacity": 2000.0})
+    runtime.initialize()
+    runtime.set("heaterOn", True)
+    runtime.step(1.0)
+
+    assert runtime.time == 1.0
+    assert runtime.get("heaterOn") is True
+    assert runtime.get("temperature") > 250.0
+    assert runtime.parameters()["roomCapacity"] == 2000.0
+
+    with pytest.raises(ValueError):
+        runtime.set_parameter("roomCapacity", 1.0)
+    with pytest.raises(KeyError):
+        runtime.get("doesNotExist")
+
+
+def test_component_simulate():
+    component = mrf.Component("SimpleThermalMVP")
+    component.initialize()
+    component.set("heaterOn", True)
+
+    result = component.simulate(10.0, 0.5)
+    assert result["time"].shape == (21,)
+    assert result["time"][-1] == 10.0
+    assert np.all(np.diff(result["temperature"]) > 0)
+    assert "heaterStatus" in result
+
+
+def test_registry_run():
+    registry = mrf.Registry()
+    registry.add("habitat", "SimpleThermalMVP", {"heaterPower": 750.0}, {"heaterOn": True})
+    assert registry.names() == ["habitat"]
+
+    result = registry.run(5.0, 1.0, ["habitat.temperature", "habitat.heaterStatus"])
+    assert result["time"].tolist() == [0.0, 1.0, 2.0, 3.0, 4.0, 5.0]
+    assert result["habitat.heaterStatus"][-1] == 1.0
+
+    values = registry.read(["habitat.temperature", "habitat.heaterPower"])
+    assert values[1] == 750.0
+
+    stats = registry.stats()["habitat"]
+    assert stats["steps"] == 5
+    assert stats["solver_calls"] == 5
+    assert 0.0 <= stats["min"] <= stats["p99"] <= stats["max"]
+
+
+def test_sweep():
+    powers = np.linspace(250.0, 1000.0, 4)
+    temps = mrf.sweep("SimpleThermalMVP", "heaterPower", powers, "temperature",
+                      stop_time=60.0, step_size=1.0, initial={"heaterOn": True})
+
+    assert temps.shape == (4, 61)
+    # More power, warmer room
+    assert np.all(np.diff(temps[:, -1]) > 0)
@@ -0,0 +1,214 @@
+use modelica_rust_ffi::recorder::glob_match;
+use modelica_rust_ffi::*;
+
+fn habitats() -> ComponentRegistry {
+    let mut registry = ComponentRegistry::new();
+    for name in ["habitat_2", "habitat_1", "greenhouse"] {
+        let mut thermal = SimpleThermalComponent::new().unwrap();
+        thermal.initialize().unwrap();
+        thermal.set_bool_input("heaterOn", true).unwrap();
+        registry.add(name.to_string(), Box::new(thermal)).unwrap();
+    }
+    registry
+}
+
+fn csv(recorder: Recorder<CsvSink<Vec<u8>>>) -> String {
+    String::from_utf8(recorder.into_sink().unwrap().into_inner()).unwrap()
+}
+
+#[test]
+fn test_glob_match() {
+    assert!(glob_match("habitat_*.temperature", "habitat_1.temperature"));
+    assert!(glob_match("*", "anything"));
+    assert!(glob_match("*.heater*", "habitat_1.heaterStatus"));
+    assert!(glob_match("habitat_?.*", "habitat_2.roomTemp"));
+    assert!(!glob_match("habitat_?.*", "habitat_12.roomTemp"));
+    assert!(!glob_match("habitat_*.temperature", "greenhouse.temperature"));
+    assert!(glob_match("a*b*c", "axxbyyc"));
+    assert!(!glob_match("a*b*c", "axxbyy"));
+}
+
+#[test]
+fn test_recorder_selection() {
+    let registry = habitats();
+    let mut recorder = Recorder::new(CsvSink::new(Vec::new()))
+        .select("habitat_*.temperature")
+        .select("*.heaterStatus");
+    recorder.start(&registry).unwrap();
+
+    assert_eq!(recorder.columns(), &[
+        "habitat_1.temperature",
+        "habitat_2.temperature",
+        "greenhouse.heaterStatus",
+        "habitat_1.heaterStatus",
+        "habitat_2.heaterStatus",
+    ]);
+
+    recorder.record(0.0, &registry).unwrap();
+    let text = csv(recorder);
+    let mut lines = text.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "time [s],habitat_1.temperature [K],habitat_2.temperature [K],greenhouse.heaterStatus,habitat_1.heaterStatus,habitat_2.heaterStatus"
+    );
+    assert_eq!(lines.next().unwrap(), "0,250,250,0,0,0");
+}
+
+#[test]
+fn test_recorder_defaults_to_outputs() {
+    let mut runtime = ModelicaRuntime::new("SimpleThermalMVP").unwrap();
+    runtime.initialize().unwrap();
+
+    let mut recorder = Recorder::new(CsvSink::new(Vec::new()));
+    recorder.start(&runtime).unwrap();
+    assert_eq!(recorder.columns(), &["temperature", "heaterStatus"]);
+
+    // Booleans and parameters can be selected explicitly
+    let mut recorder = Recorder::new(CsvSink::new(Vec::new())).select("heater*");
+    recorder.start(&runtime).unwrap();
+    assert_eq!(recorder.columns(), &["heaterPower", "heaterOn", "heaterStatus"]);
+
+    runtime.set_bool_variable("heaterOn", true).unwrap();
+    recorder.record(runtime.time(), &runtime).unwrap();
+    assert_eq!(csv(recorder).lines().nth(1).unwrap(), "0,500,1,0");
+}
+
+#[test]
+fn test_recorder_interval() {
+    let mut registry = habitats();
+    let mut recorder = Recorder::new(CsvSink::new(Vec::new()))
+        .select("habitat_1.temperature")
+        .with_interval(1.0);
+    recorder.start(&registry).unwrap();
+
+    let dt = 0.1;
+    recorder.sample(0.0, &registry).unwrap();
+    for step in 1..=25 {
+        registry.step_all(dt).unwrap();
+        recorder.sample(step as f64 * dt, &registry).unwrap();
+    }
+    // An event forces a row between output times
+    recorder.record(2.55, &registry).unwrap();
+
+    let text = csv(recorder);
+    let times: Vec<f64> = text.lines().skip(1)
+        .map(|line| line.split(',').next().unwrap().parse().unwrap())
+        .collect();
+    assert_eq!(times.len(), 4);
+    assert_eq!(times[0], 0.0);
+    assert!((times[1] - 1.0).abs() < 1e-9);
+    assert!((times[2] - 2.0).abs() < 1e-9);
+    assert_eq!(times[3], 2.55);
+}
+
+#[test]
+fn test_recorder_errors() {
+    let registry = habitats();
+
+    let mut recorder = Recorder::new(CsvSink::new(Vec::new())).select("pump_*.flow");
+    assert!(matches!(recorder.start(&registry), Err(ComponentError::VariableNotFound(_))));
+
+    let mut recorder = Recorder::new(CsvSink::new(Vec::new())).with_interval(0.0);
+    assert!(matches!(recorder.start(&registry), Err(ComponentError::InvalidInput(_))));
+
+    let mut recorder = Recorder::new(CsvSink::new(Vec::new()));
+    recorder.record(0.0, &registry).unwrap();
+    recorder.finish().unwrap();
+    assert!(matches!(recorder.record(1.0, &registry), Err(ComponentError::RecordingFailed(_))));
+}
+
+#[test]
+fn test_recorder_with_scenario() {
+    let scenario = Scenario::from_toml_str(r#"
+        step_size = 0.1
+        stop_time = 5.0
+
+        [[components]]
+        type = "SimpleThermalMVP"
+        name = "habitat_1"
+        initial = { heaterOn = true }
+    "#).unwrap();
+
+    let mut registry = scenario.build_registry().unwrap();
+    let mut recorder = Recorder::new(CsvSink::new(Vec::new())).with_interval(0.5);
+    recorder.start(&registry).unwrap();
+    scenario.run_with(&mut registry, |time, registry| recorder.sample(time, registry)).unwrap();
+
+    // Header plus t = 0, 0.5, ..., 5
+    assert_eq!(csv(recorder).lines().count(), 12);
+}
+
+/// Splits a MAT v4 file into (name, type, rows, cols, data) matrices
+fn mat_matrices(bytes: &[u8]) -> Vec<(String, i32, usize, usize, Vec<u8>)> {
+    let int = |at: usize| i32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+    let mut matrices = Vec::new();
+    let mut at = 0;
+    while at < bytes.len() {
+        let (type_code, rows, cols, name_len) = (int(at), int(at + 4) as usize, int(at + 8) as usize, int(at + 16) as usize);
+        let name = String::from_utf8(bytes[at + 20..at + 19 + name_len].to_vec()).unwrap();
+        at += 20 + name_len;
+        let size = rows * cols * match type_code { 0 => 8, 20 => 4, _ => 1 };
+        matrices.push((name, type_code, rows, cols, bytes[at..at + size].to_vec()));
+        at += size;
+    }
+    matrices
+}
+
+fn doubles(data: &[u8]) -> Vec<f64> {
+    data.chunks(8).map(|c| f64::from_le_bytes(c.try_into().unwrap())).collect()
+}
+
+#[test]
+fn test_mat_sink() {
+    let mut runtime = ModelicaRuntime::new("SimpleThermalMVP").unwrap();
+    runtime.initialize().unwrap();
+    runtime.set_bool_variable("heaterOn", true).unwrap();
+
+    let mut recorder = Recorder::new(MatSink::new(std::io::Cursor::new(Vec::new())))
+        .select("temperature")
+        .select("roomCapacity")
+        .select("heaterPower");
+    recorder.record(runtime.time(), &runtime).unwrap();
+    for _ in 0..4 {
+        runtime.step(0.5).unwrap();
+        recorder.record(runtime.time(), &runtime).unwrap();
+    }
+    let bytes = recorder.into_sink().unwrap().into_inner().into_inner();
+    let matrices = mat_matrices(&bytes);
+
+    let names: Vec<&str> = matrices.iter().map(|m| m.0.as_str()).collect();
+    assert_eq!(names, ["Aclass", "name", "description", "dataInfo", "data_1", "data_2"]);
+
+    // Aclass rows are stored column-major
+    let aclass = &matrices[0];
+    assert_eq!((aclass.2, aclass.3), (4, 11));
+    let row = |r: usize| (0..11).map(|c| aclass.4[c * 4 + r] as char).collect::<String>();
+    assert_eq!(row(0), "Atrajectory");
+    assert_eq!(row(3).trim_end(), "binTrans");
+
+    // One NUL-padded column per variable
+    let name = &matrices[1];
+    let variable = |i: usize| String::from_utf8(name.4[i * name.2..(i + 1) * name.2].to_vec()).unwrap();
+    assert_eq!(name.3, 4);
+    assert_eq!(variable(1).trim_end_matches('\0'), "temperature");
+    let description = &matrices[2];
+    assert!(String::from_utf8_lossy(&description.4).contains("[K]"));
+
+    // Fixed roomCapacity goes to data_1, tunable heaterPower to data_2
+    let info: Vec<i32> = matrices[3].4.chunks(4).map(|c| i32::from_le_bytes(c.try_into().unwrap())).collect();
+    assert_eq!(info, [0, 1, 0, -1, 2, 2, 0, -1, 1, 2, 0, 0, 2, 3, 0, -1]);
+
+    let data_1 = &matrices[4];
+    assert_eq!((data_1.2, data_1.3), (2, 2));
+    let values = doubles(&data_1.4);
+    assert_eq!(values[0], 0.0);
+    assert_eq!(values[1], values[3]);
+    assert!((values[2] - 2.0).abs() < 1e-9);
+
+    let data_2 = &matrices[5];
+    assert_eq!((data_2.2, data_2.3), (3, 5));
+    let values = doubles(&data_2.4);
+    assert!((values[12] - 2.0).abs() < 1e-9);
+    assert!(values[13] > values[1]);
+    assert_eq!(values[2], 500.0);
+}
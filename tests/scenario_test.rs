@@ -0,0 +1,174 @@
+use modelica_rust_ffi::*;
+
+const TWO_HABITATS: &str = r#"
+name = "two-habitats"
+step_size = 0.1
+stop_time = 10.0
+
+[[components]]
+type = "SimpleThermalMVP"
+name = "habitat_1"
+parameters = { heaterPower = 750.0 }
+initial = { heaterOn = true }
+
+[[components]]
+type = "SimpleThermalMVP"
+name = "habitat_2"
+
+[[connections]]
+from = "habitat_1.heaterStatus"
+to = "habitat_2.heaterOn"
+
+[[events]]
+time = 5.0
+target = "habitat_1.heaterOn"
+value = false
+"#;
+
+#[test]
+fn test_scenario_from_toml() {
+    let scenario = Scenario::from_toml_str(TWO_HABITATS).unwrap();
+    assert_eq!(scenario.name.as_deref(), Some("two-habitats"));
+    assert_eq!(scenario.components.len(), 2);
+    assert_eq!(scenario.components[0].parameters["heaterPower"], ScenarioValue::Real(750.0));
+    assert_eq!(scenario.components[0].initial["heaterOn"], ScenarioValue::Bool(true));
+    assert_eq!(scenario.connections.len(), 1);
+    assert_eq!(scenario.events.len(), 1);
+}
+
+#[test]
+fn test_scenario_from_json() {
+    let scenario = Scenario::from_json_str(r#"{
+        "step_size": 0.5,
+        "stop_time": 2.0,
+        "components": [
+            { "type": "SimpleThermalMVP", "name": "habitat_1", "initial": { "roomTemp": 300 } }
+        ]
+    }"#).unwrap();
+
+    let registry = scenario.build_registry().unwrap();
+    let habitat = registry.get_by_name("habitat_1").unwrap();
+    assert_eq!(habitat.component_type(), "SimpleThermalMVP");
+}
+
+#[test]
+fn test_scenario_run() {
+    let scenario = Scenario::from_toml_str(TWO_HABITATS).unwrap();
+
+    let mut samples = Vec::new();
+    let mut registry = scenario.build_registry().unwrap();
+    scenario.run_with(&mut registry, |time, registry| {
+        let status = registry.get_by_name("habitat_1").unwrap().get_output("heaterStatus")?;
+        samples.push((time, status));
+        Ok(())
+    }).unwrap();
+
+    // Initial sample plus one per step
+    assert_eq!(samples.len(), 101);
+    assert_eq!(samples.last().unwrap().0, 10.0);
+
+    // Heater runs until the scheduled event switches it off
+    assert_eq!(samples[10].1, 1.0);
+    assert_eq!(samples.last().unwrap().1, 0.0);
+
+    // habitat_2 was driven through the connection and warmed up too
+    let temp = registry.get_by_name("habitat_2").unwrap().get_output("temperature").unwrap();
+    assert!(temp > 250.0);
+}
+
+#[test]
+fn test_scenario_unknown_component_type() {
+    let scenario = Scenario::from_toml_str(r#"
+        step_size = 0.1
+        stop_time = 1.0
+
+        [[components]]
+        type = "DoesNotExist"
+        name = "x"
+    "#).unwrap();
+
+    assert!(matches!(scenario.build_registry(), Err(ComponentError::UnknownComponentType(_))));
+}
+
+#[test]
+fn test_scenario_validation() {
+    // Bad step size
+    let result = Scenario::from_toml_str("step_size = 0.0\nstop_time = 1.0");
+    assert!(matches!(result, Err(ComponentError::InvalidScenario(_))));
+
+    // Connection to an undeclared component
+    let result = Scenario::from_toml_str(r#"
+        step_size = 0.1
+        stop_time = 1.0
+
+        [[connections]]
+        from = "a.temperature"
+        to = "b.heaterOn"
+    "#);
+    assert!(matches!(result, Err(ComponentError::InvalidScenario(_))));
+
+    // Malformed target
+    let result = Scenario::from_toml_str(r#"
+        step_size = 0.1
+        stop_time = 1.0
+
+        [[components]]
+        type = "SimpleThermalMVP"
+        name = "habitat_1"
+
+        [[events]]
+        time = 0.5
+        target = "habitat_1"
+        value = true
+    "#);
+    assert!(matches!(result, Err(ComponentError::InvalidScenario(_))));
+
+    // Event after the stop time
+    let result = Scenario::from_toml_str(r#"
+        step_size = 0.1
+        stop_time = 1.0
+
+        [[components]]
+        type = "SimpleThermalMVP"
+        name = "habitat_1"
+
+        [[events]]
+        time = 2.0
+        target = "habitat_1.heaterOn"
+        value = true
+    "#);
+    assert!(matches!(result, Err(ComponentError::InvalidScenario(_))));
+}
+
+#[test]
+fn test_scenario_partial_last_step() {
+    let scenario = Scenario::from_toml_str(r#"
+        step_size = 0.4
+        stop_time = 1.0
+
+        [[components]]
+        type = "SimpleThermalMVP"
+        name = "habitat_1"
+
+        [[events]]
+        time = 1.0
+        target = "habitat_1.heaterOn"
+        value = true
+    "#).unwrap();
+
+    let mut times = Vec::new();
+    let mut registry = scenario.build_registry().unwrap();
+    scenario.run_with(&mut registry, |time, _| {
+        times.push(time);
+        Ok(())
+    }).unwrap();
+
+    // Two full steps, then 0.2 s to land on the stop time
+    assert_eq!(times.len(), 4);
+    assert!((times[2] - 0.8).abs() < 1e-12);
+    assert_eq!(times[3], 1.0);
+    assert!((registry.time() - 1.0).abs() < 1e-12);
+    // The event at the stop time is still applied
+    let heater = registry.get_by_name("habitat_1").unwrap().get_output("heaterOn").unwrap();
+    assert_eq!(heater, 1.0);
+}
@@ -0,0 +1,110 @@
+use modelica_rust_ffi::models::SimpleThermalMVP;
+use modelica_rust_ffi::*;
+
+/// A component without a solver
+struct Idle;
+
+impl SimulationComponent for Idle {
+    fn component_type(&self) -> &str {
+        "Idle"
+    }
+
+    fn initialize(&mut self) -> ComponentResult<()> {
+        Ok(())
+    }
+
+    fn set_input(&mut self, name: &str, _value: f64) -> ComponentResult<()> {
+        Err(ComponentError::VariableNotFound(name.to_string()))
+    }
+
+    fn set_bool_input(&mut self, name: &str, _value: bool) -> ComponentResult<()> {
+        Err(ComponentError::VariableNotFound(name.to_string()))
+    }
+
+    fn get_output(&self, name: &str) -> ComponentResult<f64> {
+        Err(ComponentError::VariableNotFound(name.to_string()))
+    }
+
+    fn step(&mut self, _dt: f64) -> ComponentResult<()> {
+        Ok(())
+    }
+
+    fn reset(&mut self) -> ComponentResult<()> {
+        Ok(())
+    }
+
+    fn metadata(&self) -> ComponentMetadata {
+        ComponentMetadata::from_variables("Idle", "Test", &[])
+    }
+}
+
+fn registry() -> ComponentRegistry {
+    let mut registry = ComponentRegistry::new();
+    for name in ["habitat_1", "habitat_2"] {
+        let mut thermal = SimpleThermalComponent::new().unwrap();
+        thermal.initialize().unwrap();
+        registry.add(name.to_string(), Box::new(thermal)).unwrap();
+    }
+    registry
+}
+
+#[test]
+fn test_step_stats() {
+    let mut registry = registry();
+    for _ in 0..10 {
+        registry.step_all(0.1).unwrap();
+    }
+
+    let stats = registry.stats();
+    assert_eq!(stats.steps, 10);
+    let names: Vec<&str> = stats.components.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["habitat_1", "habitat_2"]);
+
+    let habitat = stats.get("habitat_1").unwrap();
+    assert_eq!(habitat.steps, 10);
+    assert_eq!(habitat.solver_calls, Some(10));
+    assert!(habitat.min <= habitat.mean && habitat.mean <= habitat.max);
+    assert!(habitat.min <= habitat.p99 && habitat.p99 <= habitat.max);
+    assert!(habitat.total >= habitat.max);
+    // Without CountingAllocator as the global allocator
+    assert_eq!(habitat.allocations, None);
+
+    assert!(stats.slowest().is_some());
+    assert!(stats.total >= habitat.total);
+    assert!(stats.to_string().contains("habitat_2"));
+
+    registry.reset_stats();
+    let stats = registry.stats();
+    assert_eq!(stats.steps, 0);
+    assert_eq!(stats.get("habitat_1").unwrap().steps, 0);
+    assert_eq!(stats.get("habitat_1").unwrap().solver_calls, None);
+    assert!(stats.slowest().is_none());
+}
+
+#[test]
+fn test_stats_follow_components() {
+    let mut registry = registry();
+    let id = registry.add("idle".to_string(), Box::new(Idle)).unwrap();
+    registry.step_all(0.1).unwrap();
+
+    let stats = registry.stats();
+    assert_eq!(stats.get("idle").unwrap().steps, 1);
+    assert_eq!(stats.get("idle").unwrap().solver_calls, None);
+
+    registry.remove(id).unwrap();
+    assert!(registry.stats().get("idle").is_none());
+    assert_eq!(registry.stats().components.len(), 2);
+}
+
+#[test]
+fn test_typed_model_solver_calls() {
+    let mut registry = ComponentRegistry::new();
+    let mut thermal = SimpleThermalMVP::new().unwrap();
+    thermal.initialize().unwrap();
+    registry.add("typed".to_string(), Box::new(thermal)).unwrap();
+    for _ in 0..5 {
+        registry.step_all(0.1).unwrap();
+    }
+
+    assert_eq!(registry.stats().get("typed").unwrap().solver_calls, Some(5));
+}
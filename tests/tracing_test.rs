@@ -0,0 +1,85 @@
+#![cfg(feature = "tracing")]
+//! Run with: cargo test --features tracing --test tracing_test
+
+use modelica_rust_ffi::runtime::messages::{self, Severity};
+use modelica_rust_ffi::*;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Records span names and event fields as strings
+#[derive(Clone, Default)]
+struct Collector {
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push_str(&format!(" {}={:?}", field.name(), value));
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(format!("span {}", span.metadata().name()));
+        span.record(&mut fields);
+        let mut log = self.log.lock().unwrap();
+        log.push(fields.0);
+        Id::from_u64(log.len() as u64)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(format!("event {}", event.metadata().level()));
+        event.record(&mut fields);
+        self.log.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn test_spans_and_messages() {
+    let collector = Collector::default();
+    let log = collector.log.clone();
+
+    tracing::subscriber::with_default(collector, || {
+        let mut registry = ComponentRegistry::new();
+        registry.add("habitat_1".to_string(), Box::new(SimpleThermalComponent::new().unwrap())).unwrap();
+        registry.initialize_all().unwrap();
+        registry.step_all(0.5).unwrap();
+        registry.reset_all().unwrap();
+        messages::emit(Severity::Warning, "Variable violating max constraint");
+    });
+
+    let log = log.lock().unwrap();
+    let expected = [
+        "span initialize_all",
+        "span initialize component=\"habitat_1\"",
+        "span initialize model=\"SimpleThermalMVP\"",
+        "span step_all time=0.0 dt=0.5",
+        "span step component=\"habitat_1\"",
+        "span step model=\"SimpleThermalMVP\" time=0.0 dt=0.5",
+        "span reset_all time=0.5",
+        "span reset component=\"habitat_1\"",
+        "span reset model=\"SimpleThermalMVP\"",
+    ];
+    for line in expected {
+        assert!(log.iter().any(|l| l == line), "{:?} not in {:#?}", line, *log);
+    }
+    assert!(log.iter().any(|l| l.starts_with("event WARN")
+        && l.contains("message=Variable violating max constraint")
+        && l.contains("severity=warning")));
+}